@@ -43,6 +43,7 @@ fn render(tree: &BTreeSet<Item>, fill: f64, tick: usize, action: Action) {
     println!("fill: {fill:.02}");
     println!("len: {}", tree.len());
     println!("nodes: {}", tree.node_count());
+    println!("load factor: {:.02}", tree.load_factor());
     println!("action: {action:?}");
     println!("tick: {tick}");
     print!("branching factor: {}", tree.branching_factor());
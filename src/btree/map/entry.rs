@@ -0,0 +1,118 @@
+use std::marker::PhantomData;
+
+use crate::btree::{BTreeMap, NodePtr, deref_node, deref_node_mut, map::MapPair};
+
+/// A view into a single entry of a [`BTreeMap`], which is either vacant or occupied
+///
+/// Created by [`BTreeMap::entry`].
+pub enum Entry<'a, K: Ord, V> {
+    Vacant(VacantEntry<'a, K, V>),
+    Occupied(OccupiedEntry<'a, K, V>),
+}
+
+/// An entry of a [`BTreeMap`] whose key is not in the map yet
+pub struct VacantEntry<'a, K: Ord, V> {
+    pub(super) map: &'a mut BTreeMap<K, V>,
+    pub(super) key: K,
+}
+
+/// An entry of a [`BTreeMap`] whose key is already in the map
+pub struct OccupiedEntry<'a, K: Ord, V> {
+    pub(super) node: NodePtr<MapPair<K, V>>,
+    pub(super) idx: usize,
+    pub(super) marker: PhantomData<&'a mut BTreeMap<K, V>>,
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    #[must_use]
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Vacant(e) => e.key(),
+            Self::Occupied(e) => e.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant and returns a reference to the value
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Self::Vacant(e) => e.insert(default),
+            Self::Occupied(e) => e.into_mut(),
+        }
+    }
+
+    /// Like [`or_insert`](Self::or_insert), but only computes the default if it is needed
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Vacant(e) => e.insert(default()),
+            Self::Occupied(e) => e.into_mut(),
+        }
+    }
+
+    /// Calls `f` with the value if the entry is occupied
+    #[must_use]
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Self::Occupied(e) = &mut self {
+            f(e.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+    #[must_use]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    #[must_use]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts `value` under the key of this entry and returns a reference to it
+    pub fn insert(self, value: V) -> &'a mut V {
+        let (node, idx) = self.map.set.insert_vacant(MapPair {
+            key: self.key,
+            value,
+        });
+        &mut deref_node_mut(node).keys[idx].value
+    }
+}
+
+impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+    fn pair(&self) -> &MapPair<K, V> {
+        &deref_node(self.node).keys[self.idx]
+    }
+
+    #[must_use]
+    pub fn key(&self) -> &K {
+        &self.pair().key
+    }
+
+    #[must_use]
+    pub fn get(&self) -> &V {
+        &self.pair().value
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut deref_node_mut(self.node).keys[self.idx].value
+    }
+
+    /// Converts the entry into a reference to its value that lives as long as the map borrow
+    #[must_use]
+    pub fn into_mut(self) -> &'a mut V {
+        &mut deref_node_mut(self.node).keys[self.idx].value
+    }
+
+    /// Replaces the value of this entry and returns the old one
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+}
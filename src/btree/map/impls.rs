@@ -1,4 +1,5 @@
 use std::fmt::{Debug, Display};
+use std::ops::Index;
 
 use crate::btree::{BTreeMap, map::MapPair};
 
@@ -16,7 +17,7 @@ impl<K: PartialOrd, V> PartialOrd for MapPair<K, V> {
     }
 }
 
-impl<K: Ord, V: Clone> Ord for MapPair<K, V> {
+impl<K: Ord, V> Ord for MapPair<K, V> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.key.cmp(&other.key)
     }
@@ -28,14 +29,76 @@ impl<K: Display, V: Display> Display for MapPair<K, V> {
     }
 }
 
-impl<K: Ord + Clone + Display + Debug, V: Display + Clone + Debug> Display for BTreeMap<K, V> {
+impl<K: Ord + Display + Debug, V: Display + Debug> Display for BTreeMap<K, V> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Display::fmt(&self.set, f)
     }
 }
 
-impl<K: Ord + Clone + Debug, V: Debug + Clone> Debug for BTreeMap<K, V> {
+impl<K: Ord + Debug, V: Debug> Debug for BTreeMap<K, V> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Debug::fmt(&self.set, f)
     }
 }
+
+impl<K: Ord, V> Index<&K> for BTreeMap<K, V> {
+    type Output = V;
+
+    /// Returns the value for `key`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the map.
+    fn index(&self, key: &K) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::marker::PhantomData;
+
+    use serde::de::{MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::btree::{BTreeMap, DEFAULT_BRANCH_FACTOR};
+
+    impl<K: Ord + Serialize, V: Serialize> Serialize for BTreeMap<K, V> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(self.iter())
+        }
+    }
+
+    struct MapVisitor<K, V>(PhantomData<(K, V)>);
+
+    impl<'de, K, V> Visitor<'de> for MapVisitor<K, V>
+    where
+        K: Ord + Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        type Value = BTreeMap<K, V>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a map")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            // the branching factor is not part of the data
+            let mut map = BTreeMap::new(DEFAULT_BRANCH_FACTOR);
+            while let Some((key, value)) = access.next_entry()? {
+                map.insert(key, value);
+            }
+            Ok(map)
+        }
+    }
+
+    impl<'de, K, V> Deserialize<'de> for BTreeMap<K, V>
+    where
+        K: Ord + Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(MapVisitor(PhantomData))
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::btree::{BTreeMap, DEFAULT_BRANCH_FACTOR};
+use crate::btree::{BTreeMap, DEFAULT_BRANCH_FACTOR, Entry, test_support::Keyed};
 
 #[test]
 fn test_btree_map_new() {
@@ -49,3 +49,332 @@ fn test_btree_map_insert_weird_key() {
         assert_eq!(Some(*i), bm.remove(&f(*i)))
     }
 }
+
+#[test]
+fn test_btree_map_entry_word_count() {
+    let text = "the quick brown fox jumps over the lazy dog the end";
+    let mut counts: BTreeMap<&str, u32> = BTreeMap::new(2);
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+
+    assert_eq!(counts.len(), 9);
+    assert_eq!(counts.get(&"the"), Some(&3));
+    assert_eq!(counts.get(&"fox"), Some(&1));
+    assert_eq!(counts.get(&"cat"), None);
+}
+
+#[test]
+fn test_btree_map_entry() {
+    let mut bm: BTreeMap<u32, String> = BTreeMap::new(2);
+    for i in 0..20 {
+        bm.insert(i, i.to_string());
+    }
+
+    assert!(matches!(bm.entry(5), Entry::Occupied(_)));
+    assert!(matches!(bm.entry(50), Entry::Vacant(_)));
+    assert_eq!(bm.entry(50).key(), &50);
+
+    bm.entry(5)
+        .and_modify(|v| v.push('!'))
+        .or_insert_with(|| unreachable!());
+    assert_eq!(bm.get(&5).unwrap(), "5!");
+
+    bm.entry(50)
+        .and_modify(|_| unreachable!())
+        .or_insert_with(|| "new".to_string());
+    assert_eq!(bm.get(&50).unwrap(), "new");
+    assert_eq!(bm.len(), 21);
+
+    if let Entry::Occupied(mut e) = bm.entry(7) {
+        assert_eq!(e.insert("seven".to_string()), "7");
+    }
+    assert_eq!(bm.get(&7).unwrap(), "seven");
+    assert_eq!(bm.entry(60).or_default(), "");
+}
+
+#[test]
+fn test_btree_map_iter() {
+    let mut bm: BTreeMap<u32, String> = BTreeMap::new(2);
+    for i in [10, 20, 5, 6, 12, 30, 7, 17] {
+        bm.insert(i, format!("v{i}"));
+    }
+
+    let pairs: std::vec::Vec<_> = bm.iter().map(|(k, v)| (*k, v.clone())).collect();
+    let mut expected: std::vec::Vec<_> = [10, 20, 5, 6, 12, 30, 7, 17]
+        .into_iter()
+        .map(|i| (i, format!("v{i}")))
+        .collect();
+    expected.sort();
+    assert_eq!(pairs, expected);
+    assert_eq!(BTreeMap::<u32, u32>::new(2).iter().count(), 0);
+}
+
+#[test]
+fn test_btree_map_iter_mut() {
+    let mut bm: BTreeMap<u32, u32> = BTreeMap::new(2);
+    for i in [10, 20, 5, 6, 12, 30, 7, 17] {
+        bm.insert(i, i);
+    }
+
+    for (k, v) in bm.iter_mut() {
+        assert_eq!(k, v);
+        *v *= 10;
+    }
+    for (k, v) in bm.iter() {
+        assert_eq!(*v, k * 10);
+    }
+
+    for v in bm.values_mut() {
+        *v += 1;
+    }
+    let values: std::vec::Vec<_> = bm.iter().map(|(_, v)| *v).collect();
+    assert_eq!(values, [51, 61, 71, 101, 121, 171, 201, 301]);
+}
+
+#[test]
+fn test_btree_map_keys_values() {
+    let data = [10, 20, 5, 6, 12, 30, 7, 17];
+    let mut bm: BTreeMap<u32, String> = BTreeMap::new(2);
+    for i in data {
+        bm.insert(i, format!("v{i}"));
+    }
+
+    let mut sorted = data.to_vec();
+    sorted.sort();
+    assert_eq!(bm.keys().cloned().collect::<std::vec::Vec<_>>(), sorted);
+    assert_eq!(
+        bm.values().cloned().collect::<std::vec::Vec<_>>(),
+        sorted
+            .iter()
+            .map(|i| format!("v{i}"))
+            .collect::<std::vec::Vec<_>>()
+    );
+}
+
+#[test]
+fn test_btree_map_range() {
+    use std::ops::Bound;
+
+    let mut bm: BTreeMap<u32, u32> = BTreeMap::new(2);
+    for i in 0..50 {
+        bm.insert(i * 2, i);
+    }
+
+    let keys = |it: &mut dyn Iterator<Item = (&u32, &u32)>| {
+        it.map(|(k, _)| *k).collect::<std::vec::Vec<_>>()
+    };
+    assert_eq!(keys(&mut bm.range(10..16)), [10, 12, 14]);
+    assert_eq!(keys(&mut bm.range(10..=16)), [10, 12, 14, 16]);
+    assert_eq!(keys(&mut bm.range(9..=15)), [10, 12, 14]);
+    assert_eq!(keys(&mut bm.range(..5)), [0, 2, 4]);
+    assert_eq!(keys(&mut bm.range(94..)), [94, 96, 98]);
+    assert_eq!(
+        keys(&mut bm.range((Bound::Excluded(10), Bound::Excluded(16)))),
+        [12, 14]
+    );
+    assert_eq!(keys(&mut bm.range(..)).len(), 50);
+    assert!(keys(&mut bm.range(100..)).is_empty());
+
+    let values: std::vec::Vec<_> = bm.range(20..24).map(|(_, v)| *v).collect();
+    assert_eq!(values, [10, 11]);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_btree_map_serde_roundtrip() {
+    let mut bm: BTreeMap<String, u32> = BTreeMap::new(2);
+    for (k, v) in [("b", 2), ("a", 1), ("c", 3)] {
+        bm.insert(k.to_string(), v);
+    }
+    let json = serde_json::to_string(&bm).unwrap();
+    assert_eq!(json, r#"{"a":1,"b":2,"c":3}"#);
+
+    let back: BTreeMap<String, u32> = serde_json::from_str(&json).unwrap();
+    assert!(back.iter().eq(bm.iter()));
+    assert!(serde_json::from_str::<BTreeMap<String, u32>>("[]").is_err());
+}
+
+#[test]
+fn test_btree_map_get_or_insert_with() {
+    let mut bm: BTreeMap<u32, std::vec::Vec<u32>> = BTreeMap::new(2);
+    let mut calls = 0;
+    for i in 0..10 {
+        bm.get_or_insert_with(i % 3, || {
+            calls += 1;
+            std::vec::Vec::new()
+        })
+        .push(i);
+    }
+    assert_eq!(calls, 3);
+    assert_eq!(bm.len(), 3);
+    assert_eq!(bm.get(&0).unwrap(), &[0, 3, 6, 9]);
+    assert_eq!(bm.get(&1).unwrap(), &[1, 4, 7]);
+    assert_eq!(bm.get(&2).unwrap(), &[2, 5, 8]);
+}
+
+#[test]
+fn test_btree_map_key_value() {
+    let mut bm = BTreeMap::new(2);
+    for (id, label) in [
+        (1, "one"),
+        (2, "two"),
+        (3, "three"),
+        (4, "four"),
+        (5, "five"),
+    ] {
+        bm.insert(Keyed::new(id, label), id * 10);
+    }
+
+    let (k, v) = bm.get_key_value(&Keyed::new(3, "")).unwrap();
+    assert_eq!(k.payload, "three");
+    assert_eq!(*v, 30);
+    assert!(bm.get_key_value(&Keyed::new(9, "")).is_none());
+
+    let (k, v) = bm.remove_entry(&Keyed::new(4, "")).unwrap();
+    assert_eq!(k.payload, "four");
+    assert_eq!(v, 40);
+    assert_eq!(bm.len(), 4);
+    assert!(bm.remove_entry(&Keyed::new(4, "")).is_none());
+    assert_eq!(bm.remove(&Keyed::new(1, "")), Some(10));
+}
+
+#[test]
+fn test_btree_map_clear_keeps_degree() {
+    let mut bm: BTreeMap<u32, u32> = BTreeMap::new(3);
+    let degree = bm.set.props.degree;
+    for _ in 0..5 {
+        for i in 0..100 {
+            bm.insert(i, i);
+        }
+        bm.clear();
+        assert!(bm.is_empty());
+        assert_eq!(bm.set.props.degree, degree);
+    }
+}
+
+#[test]
+fn test_btree_map_first_last() {
+    let mut bm: BTreeMap<u32, String> = BTreeMap::new(2);
+    assert_eq!(bm.first_key_value(), None);
+    assert_eq!(bm.pop_last(), None);
+    for i in [10, 20, 5, 6, 12, 30, 7, 17] {
+        bm.insert(i, i.to_string());
+    }
+
+    assert_eq!(bm.first_key_value(), Some((&5, &"5".to_string())));
+    assert_eq!(bm.last_key_value(), Some((&30, &"30".to_string())));
+    assert_eq!(bm.pop_first(), Some((5, "5".to_string())));
+    assert_eq!(bm.pop_last(), Some((30, "30".to_string())));
+    assert_eq!(bm.len(), 6);
+    assert_eq!(bm.first_key_value().map(|(k, _)| *k), Some(6));
+    assert_eq!(bm.last_key_value().map(|(k, _)| *k), Some(20));
+    assert!(!bm.contains_key(&5));
+}
+
+#[test]
+fn test_btree_map_retain() {
+    let mut bm: BTreeMap<u32, u32> = BTreeMap::new(2);
+    for i in 0..50 {
+        bm.insert(i, i * 10);
+    }
+    bm.retain(|k, v| {
+        *v += 1;
+        k % 2 == 0
+    });
+    assert_eq!(bm.len(), 25);
+    for (k, v) in bm.iter() {
+        assert_eq!(k % 2, 0);
+        assert_eq!(*v, k * 10 + 1);
+    }
+    bm.set.assert_invariants();
+}
+
+#[test]
+fn test_btree_map_range_mut() {
+    let mut bm: BTreeMap<u32, u32> = BTreeMap::new(2);
+    for i in 0..50 {
+        bm.insert(i, i);
+    }
+
+    for (_, v) in bm.range_mut(10..20) {
+        *v *= 2;
+    }
+
+    for i in 0..50 {
+        let expected = if (10..20).contains(&i) { i * 2 } else { i };
+        assert_eq!(bm.get(&i), Some(&expected));
+    }
+    assert_eq!(bm.range_mut(10..20).count(), 10);
+    assert_eq!(bm.range_mut(60..).count(), 0);
+}
+
+#[test]
+fn test_btree_map_index() {
+    let mut bm: BTreeMap<u32, &str> = BTreeMap::new(2);
+    bm.insert(1, "one");
+    bm.insert(2, "two");
+    assert_eq!(bm[&1], "one");
+    assert_eq!(bm[&2], "two");
+}
+
+#[test]
+#[should_panic]
+fn test_btree_map_index_missing() {
+    let mut bm: BTreeMap<u32, &str> = BTreeMap::new(2);
+    bm.insert(1, "one");
+    let _ = bm[&3];
+}
+
+#[test]
+fn test_btree_map_vacant_insert_position() {
+    // the reference from a vacant insert must point at the new value, also when it splits nodes
+    let mut bm: BTreeMap<u32, u32> = BTreeMap::new(2);
+    for i in (0..300).map(|i| (i * 37) % 300) {
+        let value = bm.entry(i).or_insert(0);
+        *value = i * 2;
+    }
+    bm.set.assert_invariants();
+    for i in 0..300 {
+        assert_eq!(bm.get(&i), Some(&(i * 2)));
+    }
+}
+
+#[test]
+fn test_btree_map_remove_many() {
+    let mut bm: BTreeMap<u32, String> = BTreeMap::new(2);
+    for i in 0..500 {
+        bm.insert(i, i.to_string());
+    }
+    for i in (0..500).step_by(3) {
+        assert_eq!(bm.remove_entry(&i), Some((i, i.to_string())));
+        assert_eq!(bm.remove(&i), None);
+    }
+    bm.set.assert_invariants();
+    assert_eq!(bm.len(), 333);
+    assert!(bm.keys().copied().eq((0..500).filter(|i| i % 3 != 0)));
+}
+
+#[test]
+fn test_btree_map_without_clone() {
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Id(u32);
+    #[derive(Debug, PartialEq)]
+    struct Blob(std::vec::Vec<u8>);
+
+    let mut bm: BTreeMap<Id, Blob> = BTreeMap::new(2);
+    for i in 0..100 {
+        bm.insert(Id(i), Blob(vec![i as u8; 4]));
+    }
+    bm.entry(Id(200)).or_insert(Blob(vec![]));
+    if let Entry::Occupied(mut e) = bm.entry(Id(5)) {
+        e.get_mut().0.push(0);
+    }
+
+    assert_eq!(bm[&Id(5)].0.len(), 5);
+    assert_eq!(bm.remove(&Id(7)), Some(Blob(vec![7; 4])));
+    bm.retain(|k, _| k.0 % 2 == 0);
+    assert_eq!(bm.len(), 51);
+    assert_eq!(bm.pop_last().map(|(k, _)| k), Some(Id(200)));
+    bm.set.assert_invariants();
+}
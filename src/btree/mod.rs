@@ -1,13 +1,20 @@
 mod map;
 mod set;
-use std::ptr::NonNull;
+#[cfg(test)]
+mod test_support;
+use std::{
+    cmp::Ordering,
+    ops::{Bound, RangeBounds},
+    ptr::NonNull,
+};
 
 use crate::vec::Vec;
 
-pub use map::BTreeMap;
+pub use map::{BTreeMap, Entry, OccupiedEntry, VacantEntry};
 pub use set::BTreeSet;
 
-#[derive(Clone, PartialEq, Eq)]
+// NOTE: Node must not be Clone, copying the child pointers would lead to a double free
+#[derive(PartialEq, Eq)]
 pub(crate) struct Node<T: Ord> {
     keys: Vec<T>,
     parent: Option<NodePtr<T>>,
@@ -46,3 +53,21 @@ fn deref_node<'a, T: Ord + 'a>(p: NodePtr<T>) -> &'a Node<T> {
 fn deref_node_mut<'a, T: Ord + 'a>(p: NodePtr<T>) -> &'a mut Node<T> {
     unsafe { &mut *p.as_ptr() }
 }
+
+/// Whether `key` comes before the start of `range`
+fn is_before_range<K: Ord>(range: &impl RangeBounds<K>, key: &K) -> bool {
+    match range.start_bound() {
+        Bound::Included(s) => key.cmp(s) == Ordering::Less,
+        Bound::Excluded(s) => key.cmp(s) != Ordering::Greater,
+        Bound::Unbounded => false,
+    }
+}
+
+/// Whether `key` comes before the end of `range`
+fn is_before_range_end<K: Ord>(range: &impl RangeBounds<K>, key: &K) -> bool {
+    match range.end_bound() {
+        Bound::Included(e) => key.cmp(e) != Ordering::Greater,
+        Bound::Excluded(e) => key.cmp(e) == Ordering::Less,
+        Bound::Unbounded => true,
+    }
+}
@@ -1,7 +1,9 @@
 use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
 
-use super::{BTreeSet, Node, NodePtr, deref_node, deref_node_mut};
+use crate::btree::DEFAULT_BRANCH_FACTOR;
+
+use super::{BTreeSet, Node, NodePtr, OpNodePtr, deref_node, deref_node_mut};
 
 impl<T: Ord> Drop for Node<T> {
     fn drop(&mut self) {
@@ -11,28 +13,88 @@ impl<T: Ord> Drop for Node<T> {
     }
 }
 
-impl<T: Ord + Clone> Drop for BTreeSet<T> {
+impl<T: Ord> Drop for BTreeSet<T> {
     fn drop(&mut self) {
         Node::drop(self.root);
     }
 }
 
-// Simple iterator implementation
+impl<T: Ord + Clone> Clone for BTreeSet<T> {
+    fn clone(&self) -> Self {
+        fn clone_node<T: Ord + Clone>(node_ptr: NodePtr<T>, parent: OpNodePtr<T>) -> NodePtr<T> {
+            let node = deref_node(node_ptr);
+            let new_ptr = Node {
+                keys: node.keys.clone(),
+                parent,
+                children: crate::vec::Vec::with_capacity(node.children.capacity()),
+            }
+            .store_on_heap();
+            for child_ptr in &node.children {
+                let child = clone_node(*child_ptr, Some(new_ptr));
+                deref_node_mut(new_ptr).children.push(child);
+            }
+            new_ptr
+        }
+
+        Self {
+            root: clone_node(self.root, None),
+            props: self.props,
+        }
+    }
+}
+
+/// Iterator over the elements of a [`BTreeSet`] in ascending order
+///
+/// Walks the tree with two stacks, one from the front and one from the back. Both sides
+/// remember the position they yielded last, so they stop once they meet.
 pub struct BTreeIter<'a, T: Ord> {
     stack: Vec<(NodePtr<T>, usize)>,
+    back_stack: Vec<(NodePtr<T>, usize)>,
+    front_last: Option<(NodePtr<T>, usize)>,
+    back_last: Option<(NodePtr<T>, usize)>,
     marker: PhantomData<&'a ()>,
 }
 
 impl<'a, T: Ord> BTreeIter<'a, T> {
-    pub(crate) fn new(root_ptr: &'a NodePtr<T>) -> Self {
-        let mut iter = BTreeIter {
+    fn empty() -> Self {
+        BTreeIter {
             stack: Vec::new(),
+            back_stack: Vec::new(),
+            front_last: None,
+            back_last: None,
             marker: PhantomData,
-        };
+        }
+    }
+
+    pub(crate) fn new(root_ptr: &'a NodePtr<T>) -> Self {
+        let mut iter = Self::empty();
+        iter.push_right_path(*root_ptr);
         iter.push_left_path(root_ptr, 0);
         iter
     }
 
+    /// Creates an iterator that starts at the first element for which `is_before` is false
+    ///
+    /// `is_before` must be true for a prefix of the sorted elements and false for the rest. Only
+    /// descends the tree once, instead of walking through all smaller elements.
+    ///
+    /// Only the front stack is set up, so iterating the result from the back yields nothing.
+    pub(crate) fn starting_at(root_ptr: &'a NodePtr<T>, is_before: impl Fn(&T) -> bool) -> Self {
+        let mut iter = Self::empty();
+        let mut node = deref_node(*root_ptr);
+        loop {
+            let idx = node.keys.partition_point(&is_before);
+            // if all keys are before the start, idx is out of range and `next` skips this node,
+            // but the child we descend into may still hold later elements
+            iter.stack.push((node.as_ptr(), idx));
+            if node.is_leaf() {
+                break;
+            }
+            node = deref_node(node.children[idx]);
+        }
+        iter
+    }
+
     fn push_left_path(&mut self, node_ptr: &'a NodePtr<T>, start_idx: usize) {
         let mut node = deref_node_mut(*node_ptr);
         loop {
@@ -43,17 +105,27 @@ impl<'a, T: Ord> BTreeIter<'a, T> {
             node = deref_node_mut(node.children[start_idx]);
         }
     }
-}
 
-impl<'a, T: Ord + 'a> Iterator for BTreeIter<'a, T> {
-    type Item = &'a T;
+    /// Pushes the path to the largest element of the subtree, an entry `(node, idx)` on the back
+    /// stack means that `node.keys[idx - 1]` comes next
+    fn push_right_path(&mut self, node_ptr: NodePtr<T>) {
+        let mut node = deref_node(node_ptr);
+        loop {
+            self.back_stack.push((node.as_ptr(), node.keys.len()));
+            if node.is_leaf() {
+                break;
+            }
+            node = deref_node(node.children[node.children.len() - 1]);
+        }
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
+impl<'a, T: Ord + 'a> BTreeIter<'a, T> {
+    /// Advances to the next key and returns its node and index in that node
+    pub(crate) fn next_position(&mut self) -> Option<(NodePtr<T>, usize)> {
         while let Some((node_ptr, idx)) = self.stack.pop() {
             let node = deref_node(node_ptr);
             if idx < node.keys.len() {
-                let key = &node.keys[idx];
-
                 if idx + 1 < node.keys.len() {
                     self.stack.push((node_ptr, idx + 1));
                 }
@@ -62,14 +134,129 @@ impl<'a, T: Ord + 'a> Iterator for BTreeIter<'a, T> {
                     self.push_left_path(&node.children[idx + 1], 0);
                 }
 
-                return Some(key);
+                if self.back_last == Some((node_ptr, idx)) {
+                    // the back already yielded this one
+                    self.stack.clear();
+                    return None;
+                }
+                self.front_last = Some((node_ptr, idx));
+                return Some((node_ptr, idx));
             }
         }
         None
     }
+
+    /// Advances to the previous key from the back and returns its node and index in that node
+    pub(crate) fn next_back_position(&mut self) -> Option<(NodePtr<T>, usize)> {
+        while let Some((node_ptr, idx)) = self.back_stack.pop() {
+            if idx == 0 {
+                continue;
+            }
+            let node = deref_node(node_ptr);
+            let key_idx = idx - 1;
+            if key_idx > 0 {
+                self.back_stack.push((node_ptr, key_idx));
+            }
+
+            if !node.is_leaf() {
+                self.push_right_path(node.children[key_idx]);
+            }
+
+            if self.front_last == Some((node_ptr, key_idx)) {
+                // the front already yielded this one
+                self.back_stack.clear();
+                return None;
+            }
+            self.back_last = Some((node_ptr, key_idx));
+            return Some((node_ptr, key_idx));
+        }
+        None
+    }
+}
+
+impl<'a, T: Ord + 'a> Iterator for BTreeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_position()
+            .map(|(node_ptr, idx)| &deref_node(node_ptr).keys[idx])
+    }
 }
 
-impl<T: Ord + Clone + Debug> Debug for Node<T> {
+impl<'a, T: Ord + 'a> DoubleEndedIterator for BTreeIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_position()
+            .map(|(node_ptr, idx)| &deref_node(node_ptr).keys[idx])
+    }
+}
+
+/// Draining iterator of a [`BTreeSet`], created by [`BTreeSet::drain`]
+pub struct Drain<'a, T: Ord> {
+    pub(crate) set: &'a mut BTreeSet<T>,
+}
+
+impl<T: Ord> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.set.pop_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.set.len(), Some(self.set.len()))
+    }
+}
+
+impl<T: Ord> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        // drop the rest in one go instead of rebalancing for every element
+        *self.set = BTreeSet::new(self.set.branching_factor());
+    }
+}
+
+/// Owning iterator of a [`BTreeSet`], yields the elements in ascending order
+pub struct IntoIter<T: Ord> {
+    pub(crate) set: BTreeSet<T>,
+}
+
+impl<T: Ord> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.set.pop_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.set.len(), Some(self.set.len()))
+    }
+}
+
+impl<T: Ord> IntoIterator for BTreeSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { set: self }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BTreeSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new(DEFAULT_BRANCH_FACTOR);
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord> Extend<T> for BTreeSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.insert(element);
+        }
+    }
+}
+
+impl<T: Ord + Debug> Debug for Node<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut children = Vec::with_capacity(self.children.len());
         for child_ptr in &self.children {
@@ -85,7 +272,7 @@ impl<T: Ord + Clone + Debug> Debug for Node<T> {
     }
 }
 
-impl<T: Ord + Clone + Debug> Debug for BTreeSet<T> {
+impl<T: Ord + Debug> Debug for BTreeSet<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BTree")
             .field("props", &self.props)
@@ -94,9 +281,9 @@ impl<T: Ord + Clone + Debug> Debug for BTreeSet<T> {
     }
 }
 
-impl<T: Ord + Clone + Display + Debug> Display for BTreeSet<T> {
+impl<T: Ord + Display + Debug> Display for BTreeSet<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        fn render_child<T: Ord + Clone + Display + Debug>(
+        fn render_child<T: Ord + Display + Debug>(
             node: &Node<T>,
             depth: usize,
             buffer: &mut String,
@@ -119,3 +306,44 @@ impl<T: Ord + Clone + Display + Debug> Display for BTreeSet<T> {
         write!(f, "{buf}")
     }
 }
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::marker::PhantomData;
+
+    use serde::de::{SeqAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::btree::{BTreeSet, DEFAULT_BRANCH_FACTOR};
+
+    impl<T: Ord + Serialize> Serialize for BTreeSet<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    struct SetVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Ord + Deserialize<'de>> Visitor<'de> for SetVisitor<T> {
+        type Value = BTreeSet<T>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a sequence")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            // the branching factor is not part of the data
+            let mut set = BTreeSet::new(DEFAULT_BRANCH_FACTOR);
+            while let Some(item) = seq.next_element()? {
+                set.insert(item);
+            }
+            Ok(set)
+        }
+    }
+
+    impl<'de, T: Ord + Deserialize<'de>> Deserialize<'de> for BTreeSet<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_seq(SetVisitor(PhantomData))
+        }
+    }
+}
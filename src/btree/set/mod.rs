@@ -1,16 +1,23 @@
-use std::{fmt::Debug, mem};
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    mem,
+    ops::{Bound, RangeBounds},
+};
 
-use impls::BTreeIter;
+pub(crate) use impls::BTreeIter;
+use impls::Drain;
 
 use crate::{
-    btree::{Node, NodePtr, OpNodePtr, deref_node, deref_node_mut},
+    btree::{
+        Node, NodePtr, OpNodePtr, deref_node, deref_node_mut, is_before_range, is_before_range_end,
+    },
     vec::Vec,
 };
 
 mod impls;
 
-#[derive(Clone)]
-pub struct BTreeSet<T: Ord + Clone> {
+pub struct BTreeSet<T: Ord> {
     pub(crate) root: NodePtr<T>,
     pub(crate) props: BTreeProperties,
 }
@@ -31,7 +38,7 @@ impl BTreeProperties {
         Self {
             degree,
             max_keys: degree - 1,
-            min_keys: degree / 2,
+            min_keys: (degree - 1) / 2,
             mid_key_index: (degree - 1) / 2,
             len: 0,
         }
@@ -53,6 +60,9 @@ impl BTreeProperties {
         let new_child_node =
             Node::new_with_data(self.degree, right_keys, right_children, Some(parent_ptr))
                 .store_on_heap();
+        for grandchild_ptr in &deref_node(new_child_node).children {
+            deref_node_mut(*grandchild_ptr).parent = Some(new_child_node);
+        }
 
         parent.keys.insert(child_index, middle_key);
         parent.children.insert(child_index + 1, new_child_node);
@@ -70,12 +80,14 @@ impl BTreeProperties {
         }
     }
 
-    fn insert_non_full<T: Ord>(&self, node_ptr: NodePtr<T>, key: T) {
+    /// Inserts `key` into the subtree of `node_ptr` and returns the node and index it was placed at
+    fn insert_non_full<T: Ord>(&self, node_ptr: NodePtr<T>, key: T) -> (NodePtr<T>, usize) {
         let node = deref_node_mut(node_ptr);
         let index = Self::find_insertion_index(&node.keys, &key);
 
         if node.is_leaf() {
             node.keys.insert(index, key);
+            (node_ptr, index)
         } else if self.is_full(&node.children[index]) {
             self.split_child(node_ptr, index);
             // After split, determine which child to recurse into
@@ -84,9 +96,9 @@ impl BTreeProperties {
             } else {
                 index
             };
-            self.insert_non_full(node.children[final_index], key);
+            self.insert_non_full(node.children[final_index], key)
         } else {
-            self.insert_non_full(node.children[index], key);
+            self.insert_non_full(node.children[index], key)
         }
     }
 }
@@ -121,7 +133,7 @@ impl<T: Ord> Node<T> {
     }
 }
 
-impl<T: Ord + Clone> BTreeSet<T> {
+impl<T: Ord> BTreeSet<T> {
     pub fn new(branch_factor: usize) -> Self {
         let degree = 2 * branch_factor;
         Self {
@@ -133,22 +145,36 @@ impl<T: Ord + Clone> BTreeSet<T> {
     pub fn clear(&mut self) {
         // NOTE: seems like this calls the Drop impl of the old tree too,
         // analysis with vanguard shows no memory leaks here.
-        *self = Self::new(self.props.degree * 2)
+        *self = Self::new(self.branching_factor())
+    }
+
+    /// Adds `key` to the set and returns whether it was newly inserted
+    ///
+    /// The set holds at most one of each element. If an equal element is already present, it is
+    /// kept as is, `key` is dropped and `false` is returned.
+    pub fn insert(&mut self, key: T) -> bool {
+        if self.contains(&key) {
+            return false;
+        }
+        self.insert_vacant(key);
+        true
     }
 
-    pub fn insert(&mut self, key: T) {
+    /// Inserts `key`, which must not be in the set yet, and returns the node and index it was
+    /// placed at
+    pub(crate) fn insert_vacant(&mut self, key: T) -> (NodePtr<T>, usize) {
         if self.props.is_full(&self.root) {
-            // Create new root and make old root its child
-            let new_root = Node::new(self.props.degree, None);
-            let mut old_root = mem::replace(deref_node_mut(self.root), new_root);
-            old_root.parent = Some(self.root);
-            deref_node_mut(self.root)
-                .children
-                .push(old_root.store_on_heap());
+            // Create new root and make old root its child, the old root stays where it is so
+            // that the parent pointers of its children remain valid
+            let old_root = self.root;
+            self.root = Node::new(self.props.degree, None).store_on_heap();
+            deref_node_mut(old_root).parent = Some(self.root);
+            deref_node_mut(self.root).children.push(old_root);
             self.props.split_child(self.root, 0);
         }
-        self.props.insert_non_full(self.root, key);
+        let position = self.props.insert_non_full(self.root, key);
         self.props.len += 1;
+        position
     }
 
     #[must_use]
@@ -167,6 +193,88 @@ impl<T: Ord + Clone> BTreeSet<T> {
         }
     }
 
+    /// Returns a reference to the stored element that is equal to `key`
+    #[must_use]
+    pub fn get(&self, key: &T) -> Option<&T> {
+        let mut current = deref_node(self.root);
+        loop {
+            match current.keys.binary_search(key) {
+                Ok(idx) => return Some(&current.keys[idx]),
+                Err(idx) => {
+                    if current.is_leaf() {
+                        return None;
+                    }
+                    current = deref_node(current.children[idx]);
+                }
+            }
+        }
+    }
+
+    /// Removes the stored element that is equal to `key` and returns it
+    pub fn take(&mut self, key: &T) -> Option<T> {
+        self.remove(key)
+    }
+
+    /// Adds `key` to the set, replacing and returning an equal element that was already stored
+    pub fn replace(&mut self, key: T) -> Option<T> {
+        let mut current = deref_node_mut(self.root);
+        loop {
+            match current.keys.binary_search(&key) {
+                Ok(idx) => return Some(mem::replace(&mut current.keys[idx], key)),
+                Err(idx) => {
+                    if current.is_leaf() {
+                        break;
+                    }
+                    current = deref_node_mut(current.children[idx]);
+                }
+            }
+        }
+        self.insert(key);
+        None
+    }
+
+    /// Returns the largest element that is less than or equal to `key`
+    #[must_use]
+    pub fn floor(&self, key: &T) -> Option<&T> {
+        let mut best = None;
+        let mut current = deref_node(self.root);
+        loop {
+            match current.keys.binary_search(key) {
+                Ok(idx) => return Some(&current.keys[idx]),
+                Err(idx) => {
+                    if idx > 0 {
+                        best = Some(&current.keys[idx - 1]);
+                    }
+                    if current.is_leaf() {
+                        return best;
+                    }
+                    current = deref_node(current.children[idx]);
+                }
+            }
+        }
+    }
+
+    /// Returns the smallest element that is greater than or equal to `key`
+    #[must_use]
+    pub fn ceil(&self, key: &T) -> Option<&T> {
+        let mut best = None;
+        let mut current = deref_node(self.root);
+        loop {
+            match current.keys.binary_search(key) {
+                Ok(idx) => return Some(&current.keys[idx]),
+                Err(idx) => {
+                    if idx < current.keys.len() {
+                        best = Some(&current.keys[idx]);
+                    }
+                    if current.is_leaf() {
+                        return best;
+                    }
+                    current = deref_node(current.children[idx]);
+                }
+            }
+        }
+    }
+
     #[must_use]
     pub fn is_empty(&self) -> bool {
         deref_node(self.root).keys.is_empty()
@@ -207,6 +315,10 @@ impl<T: Ord + Clone> BTreeSet<T> {
         }
     }
 
+    /// Mutable access to the smallest element
+    ///
+    /// Only mutate the element in ways that preserve its ordering relative to the other elements,
+    /// otherwise lookups and removals in the set will misbehave.
     #[must_use]
     pub fn first_mut(&mut self) -> Option<&mut T> {
         if self.is_empty() {
@@ -237,15 +349,19 @@ impl<T: Ord + Clone> BTreeSet<T> {
         }
     }
 
+    /// Mutable access to the largest element
+    ///
+    /// Only mutate the element in ways that preserve its ordering relative to the other elements,
+    /// otherwise lookups and removals in the set will misbehave.
     #[must_use]
-    pub fn last_mut(&self) -> Option<&T> {
+    pub fn last_mut(&mut self) -> Option<&mut T> {
         if self.is_empty() {
             return None;
         }
         let mut current = deref_node_mut(self.root);
         loop {
             if current.is_leaf() {
-                return Some(current.keys.last().unwrap());
+                return Some(current.keys.last_mut().unwrap());
             } else {
                 current = deref_node_mut(*current.children.last().unwrap());
             }
@@ -253,13 +369,43 @@ impl<T: Ord + Clone> BTreeSet<T> {
     }
 
     pub fn pop_first(&mut self) -> Option<T> {
-        let key = self.first().cloned()?;
-        self.remove(&key)
+        if self.is_empty() {
+            return None;
+        }
+        let key = self.remove_first_from_node(self.root);
+        self.shrink_root();
+        self.props.len -= 1;
+        Some(key)
     }
 
     pub fn pop_last(&mut self) -> Option<T> {
-        let key = self.last().cloned()?;
-        self.remove(&key)
+        if self.is_empty() {
+            return None;
+        }
+        let key = self.remove_last_from_node(self.root);
+        self.shrink_root();
+        self.props.len -= 1;
+        Some(key)
+    }
+
+    /// Moves all elements greater than or equal to `key` into a new set with the same branching
+    /// factor
+    #[must_use]
+    pub fn split_off(&mut self, key: &T) -> Self {
+        let mut other = Self::new(self.branching_factor());
+        while self.last().is_some_and(|last| last >= key) {
+            other.insert(self.pop_last().expect("set is not empty"));
+        }
+        other
+    }
+
+    /// Moves all elements of `other` into this set, leaving `other` empty
+    ///
+    /// Elements that are already in this set are dropped.
+    pub fn append(&mut self, other: &mut Self) {
+        while let Some(key) = other.pop_first() {
+            self.insert(key);
+        }
     }
 
     #[must_use]
@@ -290,15 +436,189 @@ impl<T: Ord + Clone> BTreeSet<T> {
         total
     }
 
+    /// Removes all elements and yields them in ascending order
+    ///
+    /// The set is empty afterwards, even if the iterator is dropped before it is used up.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain { set: self }
+    }
+
+    /// Returns the `n`-th smallest element, counting from 0
+    #[must_use]
+    pub fn select(&self, n: usize) -> Option<&T> {
+        // TODO: store subtree sizes in the nodes to descend directly in O(log n)
+        if n >= self.len() {
+            return None;
+        }
+        self.iter().nth(n)
+    }
+
+    /// Returns the number of elements that are smaller than `key`
+    #[must_use]
+    pub fn rank(&self, key: &T) -> usize {
+        // TODO: store subtree sizes in the nodes to count in O(log n)
+        self.iter().take_while(|&k| k < key).count()
+    }
+
+    /// Ratio of stored keys to the number of keys the current nodes could hold
+    #[must_use]
+    pub fn load_factor(&self) -> f64 {
+        self.len() as f64 / (self.node_count() * self.props.max_keys) as f64
+    }
+
     #[must_use]
     pub fn iter(&self) -> BTreeIter<'_, T> {
         BTreeIter::new(&self.root)
     }
 
-    pub fn range<'a>(&'a self, start: &T, end: &T) -> impl Iterator<Item = &'a T> {
-        self.iter()
-            .skip_while(move |&k| k < start)
-            .take_while(move |&k| k <= end)
+    /// Iterates over the elements within `range` in ascending order
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> impl Iterator<Item = &T> {
+        BTreeIter::starting_at(&self.root, |k| is_before_range(&range, k))
+            .take_while(move |k| is_before_range_end(&range, *k))
+    }
+
+    /// Iterates over the elements from `start` to `end`, both inclusive, in ascending order
+    #[deprecated = "use `range(start..=end)` instead"]
+    pub fn range_between<'a>(&'a self, start: &'a T, end: &'a T) -> impl Iterator<Item = &'a T> {
+        self.range((Bound::Included(start), Bound::Included(end)))
+    }
+
+    /// Returns how many elements are within `range`
+    #[must_use]
+    pub fn count_range<R: RangeBounds<T>>(&self, range: R) -> usize {
+        // TODO: store subtree sizes in the nodes to count in O(log n)
+        self.range(range).count()
+    }
+
+    /// Iterates over the elements that are in exactly one of `self` and `other`, in ascending
+    /// order
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> {
+        let mut a = self.iter().peekable();
+        let mut b = other.iter().peekable();
+        std::iter::from_fn(move || {
+            loop {
+                match (a.peek(), b.peek()) {
+                    (Some(x), Some(y)) => match x.cmp(y) {
+                        Ordering::Less => return a.next(),
+                        Ordering::Greater => return b.next(),
+                        Ordering::Equal => {
+                            a.next();
+                            b.next();
+                        }
+                    },
+                    (Some(_), None) => return a.next(),
+                    (None, _) => return b.next(),
+                }
+            }
+        })
+    }
+
+    /// Whether all elements of `self` are also in `other`
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        if self.len() > other.len() {
+            return false;
+        }
+        let mut b = other.iter().peekable();
+        for x in self.iter() {
+            while b.next_if(|y| *y < x).is_some() {}
+            if b.next_if(|y| *y == x).is_none() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether all elements of `other` are also in `self`
+    #[must_use]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Whether `self` and `other` have no elements in common
+    #[must_use]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        let mut a = self.iter().peekable();
+        let mut b = other.iter().peekable();
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            match x.cmp(y) {
+                Ordering::Less => {
+                    a.next();
+                }
+                Ordering::Greater => {
+                    b.next();
+                }
+                Ordering::Equal => return false,
+            }
+        }
+        true
+    }
+
+    /// Checks that the tree is a valid B-tree and panics otherwise
+    ///
+    /// Meant for debugging and tests, this walks the whole tree.
+    pub fn assert_invariants(&self) {
+        assert!(
+            deref_node(self.root).parent.is_none(),
+            "root has a parent pointer"
+        );
+        let mut leaf_depth = None;
+        let count = self.check_node(self.root, 1, &mut leaf_depth, None, None);
+        assert_eq!(count, self.len(), "len does not match the number of keys");
+    }
+
+    /// Checks the subtree of `node_ptr`, all its keys must be between `lower` and `upper`, and
+    /// returns the number of keys in it
+    fn check_node(
+        &self,
+        node_ptr: NodePtr<T>,
+        depth: usize,
+        leaf_depth: &mut Option<usize>,
+        lower: Option<&T>,
+        upper: Option<&T>,
+    ) -> usize {
+        let node = deref_node(node_ptr);
+        let keys = node.keys.as_slice();
+
+        assert!(keys.len() <= self.props.max_keys, "node has too many keys");
+        if node_ptr != self.root {
+            assert!(keys.len() >= self.props.min_keys, "node has too few keys");
+        }
+        assert!(keys.windows(2).all(|w| w[0] < w[1]), "keys are not sorted");
+        if let (Some(lower), Some(first)) = (lower, keys.first()) {
+            assert!(lower < first, "key is not greater than its separator");
+        }
+        if let (Some(upper), Some(last)) = (upper, keys.last()) {
+            assert!(last < upper, "key is not smaller than its separator");
+        }
+
+        if node.is_leaf() {
+            let expected = *leaf_depth.get_or_insert(depth);
+            assert_eq!(depth, expected, "leaves are at different depths");
+            return keys.len();
+        }
+
+        assert_eq!(
+            node.children.len(),
+            keys.len() + 1,
+            "child count does not match key count"
+        );
+        let mut count = keys.len();
+        for (i, child_ptr) in node.children.iter().enumerate() {
+            assert_eq!(
+                deref_node(*child_ptr).parent,
+                Some(node_ptr),
+                "child has a wrong parent pointer"
+            );
+            let lower = if i == 0 { lower } else { Some(&keys[i - 1]) };
+            let upper = if i == keys.len() {
+                upper
+            } else {
+                Some(&keys[i])
+            };
+            count += self.check_node(*child_ptr, depth + 1, leaf_depth, lower, upper);
+        }
+        count
     }
 
     pub const fn branching_factor(&self) -> usize {
@@ -310,15 +630,95 @@ impl<T: Ord + Clone> BTreeSet<T> {
     }
 }
 
+impl<T: Ord + Clone> BTreeSet<T> {
+    /// Builds a balanced tree from sorted data without duplicates, in O(n)
+    ///
+    /// The result is not specified if `data` is not sorted or contains duplicates, debug builds
+    /// panic in that case.
+    #[must_use]
+    pub fn from_sorted(data: &[T], branch_factor: usize) -> Self {
+        debug_assert!(
+            data.windows(2).all(|w| w[0] < w[1]),
+            "data must be sorted and free of duplicates"
+        );
+        let mut set = Self::new(branch_factor);
+        let degree = set.props.degree;
+
+        let mut height = 1;
+        while Self::subtree_capacity(degree, height) < data.len() {
+            height += 1;
+        }
+
+        Node::drop(set.root);
+        set.root = Self::build_sorted(degree, data, height, None);
+        set.props.len = data.len();
+        set
+    }
+
+    /// How many keys fit into a full subtree of the given height
+    fn subtree_capacity(degree: usize, height: u32) -> usize {
+        degree.saturating_pow(height).saturating_sub(1)
+    }
+
+    fn build_sorted(degree: usize, keys: &[T], height: u32, parent: OpNodePtr<T>) -> NodePtr<T> {
+        let node_ptr = Node::new(degree, parent).store_on_heap();
+        let node = deref_node_mut(node_ptr);
+        if height == 1 {
+            node.keys.extend(keys.iter().cloned());
+            return node_ptr;
+        }
+
+        // Use as few children as possible and spread the keys evenly between them. That way
+        // every child is at least half full, so no node ends up with too few keys.
+        let child_count = (keys.len() + 1).div_ceil(Self::subtree_capacity(degree, height - 1) + 1);
+        let child_keys = keys.len() - (child_count - 1);
+        let (base, extra) = (child_keys / child_count, child_keys % child_count);
+
+        let mut start = 0;
+        for i in 0..child_count {
+            let size = base + usize::from(i < extra);
+            let child = Self::build_sorted(
+                degree,
+                &keys[start..start + size],
+                height - 1,
+                Some(node_ptr),
+            );
+            node.children.push(child);
+            start += size;
+            if i + 1 < child_count {
+                node.keys.push(keys[start].clone());
+                start += 1;
+            }
+        }
+        node_ptr
+    }
+}
+
 // removing keys
 // this is so fucking complicated
 //
 // To be honest, this whole impl block is LLM generated.
-impl<T: Ord + Clone> BTreeSet<T> {
+impl<T: Ord> BTreeSet<T> {
     pub fn remove(&mut self, key: &T) -> Option<T> {
-        let result = self.remove_from_node(self.root, key);
+        self.remove_by(&|k: &T| k.cmp(key))
+    }
+
+    /// Removes the element for which `cmp` returns [`Ordering::Equal`] and returns it
+    ///
+    /// `cmp` compares an element of the set to the one that should be removed, this lets
+    /// [`BTreeMap`](crate::btree::BTreeMap) remove pairs by their key alone.
+    pub(crate) fn remove_by<F: Fn(&T) -> Ordering>(&mut self, cmp: &F) -> Option<T> {
+        let result = self.remove_from_node(self.root, cmp);
+        self.shrink_root();
+
+        if result.is_some() {
+            self.props.len -= 1;
+        }
+        result
+    }
 
-        // Handle root underflow - if root is empty but has children, promote the only child
+    /// Handles root underflow - if root is empty but has children, promote the only child
+    fn shrink_root(&mut self) {
         let root_node = deref_node(self.root);
         if root_node.keys.is_empty() && !root_node.children.is_empty() {
             let old_root = self.root;
@@ -331,17 +731,16 @@ impl<T: Ord + Clone> BTreeSet<T> {
             deref_node_mut(old_root).children.clear();
             Node::drop(old_root);
         }
-
-        if result.is_some() {
-            self.props.len -= 1;
-        }
-        result
     }
 
-    fn remove_from_node(&mut self, node_ptr: NodePtr<T>, key: &T) -> Option<T> {
+    fn remove_from_node<F: Fn(&T) -> Ordering>(
+        &mut self,
+        node_ptr: NodePtr<T>,
+        cmp: &F,
+    ) -> Option<T> {
         let node = deref_node_mut(node_ptr);
 
-        match node.keys.binary_search(key) {
+        match node.keys.binary_search_by(cmp) {
             Ok(idx) => {
                 // Key found in this node
                 if node.is_leaf() {
@@ -349,7 +748,7 @@ impl<T: Ord + Clone> BTreeSet<T> {
                     node.keys.remove(idx)
                 } else {
                     // Case 2: Key is in an internal node
-                    self.remove_from_internal_node(node_ptr, idx)
+                    self.remove_from_internal_node(node_ptr, idx, cmp)
                 }
             }
             Err(idx) => {
@@ -367,51 +766,87 @@ impl<T: Ord + Clone> BTreeSet<T> {
 
                         // After rebalancing, we need to search again as indices may have changed
                         let node = deref_node(node_ptr);
-                        let new_idx = match node.keys.binary_search(key) {
+                        let new_idx = match node.keys.binary_search_by(cmp) {
                             Ok(i) => {
                                 // Key moved up to this node
                                 return if node.is_leaf() {
                                     deref_node_mut(node_ptr).keys.remove(i)
                                 } else {
-                                    self.remove_from_internal_node(node_ptr, i)
+                                    self.remove_from_internal_node(node_ptr, i, cmp)
                                 };
                             }
                             Err(i) => i,
                         };
 
-                        self.remove_from_node(node.children[new_idx], key)
+                        self.remove_from_node(node.children[new_idx], cmp)
                     } else {
-                        self.remove_from_node(child_ptr, key)
+                        self.remove_from_node(child_ptr, cmp)
                     }
                 }
             }
         }
     }
 
-    fn remove_from_internal_node(&mut self, node_ptr: NodePtr<T>, key_idx: usize) -> Option<T> {
-        let node = deref_node(node_ptr);
-        let key = node.keys[key_idx].clone();
+    fn remove_from_internal_node<F: Fn(&T) -> Ordering>(
+        &mut self,
+        node_ptr: NodePtr<T>,
+        key_idx: usize,
+        cmp: &F,
+    ) -> Option<T> {
+        let node = deref_node_mut(node_ptr);
 
         let left_child = node.children[key_idx];
         let right_child = node.children[key_idx + 1];
 
         if deref_node(left_child).keys.len() > self.props.min_keys {
-            // Get predecessor
-            let predecessor = self.get_predecessor(left_child);
-            deref_node_mut(node_ptr).keys[key_idx] = predecessor.clone();
-            self.remove_from_node(left_child, &predecessor);
-            Some(key)
+            // Replace with the predecessor
+            let predecessor = self.remove_last_from_node(left_child);
+            Some(mem::replace(&mut node.keys[key_idx], predecessor))
         } else if deref_node(right_child).keys.len() > self.props.min_keys {
-            // Get successor
-            let successor = self.get_successor(right_child);
-            deref_node_mut(node_ptr).keys[key_idx] = successor.clone();
-            self.remove_from_node(right_child, &successor);
-            Some(key)
+            // Replace with the successor
+            let successor = self.remove_first_from_node(right_child);
+            Some(mem::replace(&mut node.keys[key_idx], successor))
         } else {
-            // Both children have minimum keys - merge
+            // Both children have minimum keys - merge, the key moves down into the left child
             self.merge_children(node_ptr, key_idx);
-            self.remove_from_node(left_child, &key)
+            self.remove_from_node(left_child, cmp)
+        }
+    }
+
+    /// Removes the smallest key in the subtree of `node_ptr`, keeping all visited children above
+    /// the minimum number of keys like [`remove_from_node`](Self::remove_from_node)
+    fn remove_first_from_node(&mut self, node_ptr: NodePtr<T>) -> T {
+        let node = deref_node_mut(node_ptr);
+        if node.is_leaf() {
+            return node
+                .keys
+                .remove(0)
+                .expect("nodes in a non-empty tree are never empty");
         }
+        if deref_node(node.children[0]).keys.len() <= self.props.min_keys {
+            // merging or borrowing keeps the smallest keys in the first child
+            self.ensure_child_has_enough_keys(node_ptr, 0);
+        }
+        self.remove_first_from_node(deref_node(node_ptr).children[0])
+    }
+
+    /// Removes the largest key in the subtree of `node_ptr`, keeping all visited children above
+    /// the minimum number of keys like [`remove_from_node`](Self::remove_from_node)
+    fn remove_last_from_node(&mut self, node_ptr: NodePtr<T>) -> T {
+        let node = deref_node_mut(node_ptr);
+        if node.is_leaf() {
+            return node
+                .keys
+                .pop()
+                .expect("nodes in a non-empty tree are never empty");
+        }
+        let last = node.children.len() - 1;
+        if deref_node(node.children[last]).keys.len() <= self.props.min_keys {
+            self.ensure_child_has_enough_keys(node_ptr, last);
+        }
+        // a merge with the left sibling removes a child, so look up the last one again
+        let node = deref_node(node_ptr);
+        self.remove_last_from_node(node.children[node.children.len() - 1])
     }
 
     fn ensure_child_has_enough_keys(&mut self, parent_ptr: NodePtr<T>, child_idx: usize) {
@@ -450,8 +885,6 @@ impl<T: Ord + Clone> BTreeSet<T> {
         let child_ptr = parent.children[child_idx];
         let left_sibling_ptr = parent.children[child_idx - 1];
 
-        let separator_key = parent.keys[child_idx - 1].clone();
-
         // Move a key from left sibling through parent to child
         let left_sibling = deref_node_mut(left_sibling_ptr);
         let borrowed_key = left_sibling.keys.pop().unwrap();
@@ -462,7 +895,7 @@ impl<T: Ord + Clone> BTreeSet<T> {
             None
         };
 
-        parent.keys[child_idx - 1] = borrowed_key;
+        let separator_key = mem::replace(&mut parent.keys[child_idx - 1], borrowed_key);
 
         let child = deref_node_mut(child_ptr);
         child.keys.insert(0, separator_key);
@@ -478,8 +911,6 @@ impl<T: Ord + Clone> BTreeSet<T> {
         let child_ptr = parent.children[child_idx];
         let right_sibling_ptr = parent.children[child_idx + 1];
 
-        let separator_key = parent.keys[child_idx].clone();
-
         // Move a key from right sibling through parent to child
         let right_sibling = deref_node_mut(right_sibling_ptr);
         let borrowed_key = right_sibling.keys.remove(0).unwrap();
@@ -490,7 +921,7 @@ impl<T: Ord + Clone> BTreeSet<T> {
             None
         };
 
-        parent.keys[child_idx] = borrowed_key;
+        let separator_key = mem::replace(&mut parent.keys[child_idx], borrowed_key);
 
         let child = deref_node_mut(child_ptr);
         child.keys.push(separator_key);
@@ -529,23 +960,6 @@ impl<T: Ord + Clone> BTreeSet<T> {
         // Clean up the right child node
         Node::drop(right_child_ptr);
     }
-
-    fn get_predecessor(&self, node_ptr: NodePtr<T>) -> T {
-        let mut current = deref_node(node_ptr);
-        while !current.is_leaf() {
-            let last_child_idx = current.children.len() - 1;
-            current = deref_node(current.children[last_child_idx]);
-        }
-        current.keys.last().unwrap().clone()
-    }
-
-    fn get_successor(&self, node_ptr: NodePtr<T>) -> T {
-        let mut current = deref_node(node_ptr);
-        while !current.is_leaf() {
-            current = deref_node(current.children[0]);
-        }
-        current.keys[0].clone()
-    }
 }
 
 #[cfg(test)]
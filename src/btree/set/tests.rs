@@ -1,4 +1,7 @@
-use crate::{btree::DEFAULT_BRANCH_FACTOR, trace_current_function};
+use crate::{
+    btree::{DEFAULT_BRANCH_FACTOR, test_support::Keyed},
+    trace_current_function,
+};
 
 use super::*;
 
@@ -177,10 +180,10 @@ fn test_btree_set_simple_remove() {
     assert_eq!(tree.node_count(), 4);
     assert!(tree.contains(&19));
 
-    tree.insert(19); // now it's there two times!
-    assert_eq!(tree.len(), 9);
+    assert!(!tree.insert(19)); // already there, nothing changes
+    assert_eq!(tree.len(), 8);
     assert_eq!(tree.depth(), 2);
-    assert_eq!(tree.node_count(), 5);
+    assert_eq!(tree.node_count(), 4);
     assert!(tree.contains(&19));
 }
 
@@ -268,16 +271,16 @@ fn test_btree_set_random_insertion() {
 fn test_btree_set_duplicates() {
     let mut tree = BTreeSet::new(3);
 
-    tree.insert(5);
-    tree.insert(5);
-    tree.insert(5);
+    assert!(tree.insert(5));
+    assert!(!tree.insert(5));
+    assert!(!tree.insert(5));
 
-    assert_eq!(tree.len(), 3);
+    assert_eq!(tree.len(), 1);
     assert!(tree.contains(&5));
 
-    // Should find all three instances in iteration
+    // A set holds each element only once
     let count = tree.iter().filter(|&&x| x == 5).count();
-    assert_eq!(count, 3);
+    assert_eq!(count, 1);
 }
 
 #[test]
@@ -353,6 +356,7 @@ fn test_btree_set_height_characteristics() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_btree_set_range_iteration() {
     let mut tree = BTreeSet::new(3);
     let data = (1..=100).collect::<std::vec::Vec<_>>();
@@ -362,15 +366,15 @@ fn test_btree_set_range_iteration() {
     }
 
     // Test range iteration
-    let range_25_75: std::vec::Vec<_> = tree.range(&25, &75).cloned().collect();
+    let range_25_75: std::vec::Vec<_> = tree.range_between(&25, &75).cloned().collect();
     let expected = (25..=75).collect::<std::vec::Vec<_>>();
     assert_eq!(range_25_75, expected);
 
     // Test edge cases
-    let range_1_5: std::vec::Vec<_> = tree.range(&1, &5).cloned().collect();
+    let range_1_5: std::vec::Vec<_> = tree.range_between(&1, &5).cloned().collect();
     assert_eq!(range_1_5, vec![1, 2, 3, 4, 5]);
 
-    let range_95_100: std::vec::Vec<_> = tree.range(&95, &100).cloned().collect();
+    let range_95_100: std::vec::Vec<_> = tree.range_between(&95, &100).cloned().collect();
     assert_eq!(range_95_100, vec![95, 96, 97, 98, 99, 100]);
 }
 
@@ -497,3 +501,587 @@ fn test_btree_set_edge_removals() {
     let expected = vec![2, 3, 5, 6, 7, 8, 11, 12, 13, 15, 16, 18];
     assert_eq!(remaining, expected);
 }
+
+#[test]
+fn test_btree_set_get() {
+    let mut tree = BTreeSet::new(2);
+    for (id, name) in [
+        (3, "three"),
+        (1, "one"),
+        (4, "four"),
+        (5, "five"),
+        (9, "nine"),
+        (2, "two"),
+    ] {
+        tree.insert(Keyed::new(id, name));
+    }
+
+    for (id, name) in [(1, "one"), (2, "two"), (9, "nine")] {
+        let found = tree.get(&Keyed::new(id, "")).unwrap();
+        assert_eq!(found.id, id);
+        assert_eq!(found.payload, name);
+    }
+    assert!(tree.get(&Keyed::new(7, "seven")).is_none());
+}
+
+#[test]
+fn test_btree_set_range_bounds() {
+    use std::ops::Bound;
+
+    let mut tree = BTreeSet::new(3);
+    for x in 0..100 {
+        tree.insert(x);
+    }
+
+    let collect = |it: &mut dyn Iterator<Item = &u32>| it.copied().collect::<std::vec::Vec<_>>();
+    assert_eq!(
+        collect(&mut tree.range(25..75)),
+        (25..75).collect::<std::vec::Vec<_>>()
+    );
+    assert_eq!(
+        collect(&mut tree.range(25..=75)),
+        (25..=75).collect::<std::vec::Vec<_>>()
+    );
+    assert_eq!(
+        collect(&mut tree.range(..10)),
+        (0..10).collect::<std::vec::Vec<_>>()
+    );
+    assert_eq!(
+        collect(&mut tree.range(90..)),
+        (90..100).collect::<std::vec::Vec<_>>()
+    );
+    assert_eq!(collect(&mut tree.range(..)).len(), 100);
+    assert_eq!(
+        collect(&mut tree.range((Bound::Excluded(10), Bound::Included(12)))),
+        [11, 12]
+    );
+    assert!(collect(&mut tree.range(200..)).is_empty());
+    assert!(collect(&mut tree.range(50..50)).is_empty());
+}
+
+#[test]
+fn test_btree_set_range_seeks() {
+    use std::cell::Cell;
+
+    thread_local! {
+        static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Counted(u32);
+    impl PartialOrd for Counted {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Counted {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            COMPARISONS.with(|c| c.set(c.get() + 1));
+            self.0.cmp(&other.0)
+        }
+    }
+
+    const N: u32 = 100_000;
+    let mut tree = BTreeSet::new(6);
+    for x in 0..N {
+        tree.insert(Counted(x));
+    }
+
+    COMPARISONS.with(|c| c.set(0));
+    let found: std::vec::Vec<_> = tree
+        .range(Counted(N / 2)..Counted(N / 2 + 10))
+        .map(|c| c.0)
+        .collect();
+    let comparisons = COMPARISONS.with(|c| c.get());
+
+    assert_eq!(found, (N / 2..N / 2 + 10).collect::<std::vec::Vec<_>>());
+    // a scan from the start would need at least N / 2 comparisons
+    assert!(comparisons < 200, "range needed {comparisons} comparisons");
+}
+
+#[test]
+fn test_btree_set_symmetric_difference() {
+    let mut a = BTreeSet::new(2);
+    let mut b = BTreeSet::new(2);
+    for x in [1, 2, 3] {
+        a.insert(x);
+    }
+    for x in [2, 3, 4] {
+        b.insert(x);
+    }
+    let diff: std::vec::Vec<_> = a.symmetric_difference(&b).copied().collect();
+    assert_eq!(diff, [1, 4]);
+    let diff: std::vec::Vec<_> = b.symmetric_difference(&a).copied().collect();
+    assert_eq!(diff, [1, 4]);
+
+    let empty = BTreeSet::new(2);
+    let diff: std::vec::Vec<_> = a.symmetric_difference(&empty).copied().collect();
+    assert_eq!(diff, [1, 2, 3]);
+    assert_eq!(a.symmetric_difference(&a).count(), 0);
+}
+
+#[test]
+fn test_btree_set_relations() {
+    let set = |items: &[u32]| {
+        let mut s = BTreeSet::new(2);
+        for &x in items {
+            s.insert(x);
+        }
+        s
+    };
+    let small = set(&[2, 4]);
+    let big = set(&[1, 2, 3, 4, 5]);
+    let overlap = set(&[4, 6]);
+    let other = set(&[6, 7, 8]);
+    let empty = set(&[]);
+
+    assert!(small.is_subset(&big));
+    assert!(!big.is_subset(&small));
+    assert!(big.is_superset(&small));
+    assert!(!small.is_superset(&big));
+    assert!(small.is_subset(&small));
+    assert!(empty.is_subset(&small));
+
+    assert!(!small.is_subset(&overlap));
+    assert!(!overlap.is_subset(&big));
+    assert!(!small.is_disjoint(&overlap));
+
+    assert!(small.is_disjoint(&other));
+    assert!(big.is_disjoint(&other));
+    assert!(empty.is_disjoint(&big));
+    assert!(!other.is_subset(&big));
+}
+
+#[test]
+fn test_btree_set_non_clone() {
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Unique(Box<u32>);
+
+    let mut tree = BTreeSet::new(2);
+    for i in 0..200 {
+        tree.insert(Unique(Box::new((i * 37) % 200)));
+    }
+    assert_eq!(tree.len(), 200);
+
+    for i in (0..200).step_by(3) {
+        assert_eq!(tree.remove(&Unique(Box::new(i))), Some(Unique(Box::new(i))));
+    }
+    assert_eq!(tree.pop_first(), Some(Unique(Box::new(1))));
+    assert_eq!(tree.pop_last(), Some(Unique(Box::new(199))));
+
+    let remaining: std::vec::Vec<u32> = tree.iter().map(|u| *u.0).collect();
+    let expected: std::vec::Vec<u32> = (2..199).filter(|i| i % 3 != 0).collect();
+    assert_eq!(remaining, expected);
+    assert_eq!(tree.len(), expected.len());
+
+    while tree.pop_last().is_some() {}
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn test_btree_set_load_factor() {
+    let mut tree = BTreeSet::new(3);
+    assert_eq!(tree.load_factor(), 0.0);
+    for i in 0..1000 {
+        tree.insert(i);
+    }
+    let lf = tree.load_factor();
+    assert!(lf > 0.0 && lf <= 1.0, "load factor {lf} out of range");
+
+    // a single full node
+    let mut tree = BTreeSet::new(3);
+    for i in 0..5 {
+        tree.insert(i);
+    }
+    assert_eq!(tree.node_count(), 1);
+    assert_eq!(tree.load_factor(), 1.0);
+}
+
+#[test]
+fn test_btree_set_clone_deep() {
+    fn check_parents<T: Ord>(node_ptr: NodePtr<T>) {
+        for child in &deref_node(node_ptr).children {
+            assert_eq!(deref_node(*child).parent, Some(node_ptr));
+            check_parents(*child);
+        }
+    }
+
+    let mut tree = BTreeSet::new(2);
+    for i in 0..500 {
+        tree.insert(i.to_string());
+    }
+    let mut cloned = tree.clone();
+    assert_ne!(tree.root, cloned.root);
+    assert!(deref_node(cloned.root).parent.is_none());
+    check_parents(cloned.root);
+    assert_eq!(cloned.node_count(), tree.node_count());
+
+    tree.remove(&"7".to_string());
+    drop(tree);
+
+    assert_eq!(cloned.len(), 500);
+    assert!(cloned.contains(&"7".to_string()));
+    for i in 0..250 {
+        assert_eq!(cloned.remove(&i.to_string()), Some(i.to_string()));
+    }
+    assert!(
+        cloned
+            .iter()
+            .map(|s| s.parse::<u32>().unwrap())
+            .eq((250..500).collect::<std::collections::BTreeSet<_>>())
+    );
+}
+
+#[test]
+fn test_btree_set_first_last_mut() {
+    let mut tree = BTreeSet::new(2);
+    assert!(tree.last_mut().is_none());
+    for i in 0..100u32 {
+        tree.insert(i * 10);
+    }
+
+    // moving the largest element further up keeps the order intact
+    *tree.last_mut().unwrap() += 5;
+    assert_eq!(tree.last(), Some(&995));
+    assert!(tree.contains(&995));
+    assert!(!tree.contains(&990));
+
+    *tree.first_mut().unwrap() = 1;
+    assert_eq!(tree.first(), Some(&1));
+    assert_eq!(tree.pop_last(), Some(995));
+    assert_eq!(tree.remove(&1), Some(1));
+}
+
+#[test]
+fn test_btree_set_split_off() {
+    let mut tree = BTreeSet::new(2);
+    for i in 1..=10 {
+        tree.insert(i);
+    }
+    let upper = tree.split_off(&6);
+    assert_eq!(
+        tree.iter().copied().collect::<std::vec::Vec<_>>(),
+        [1, 2, 3, 4, 5]
+    );
+    assert_eq!(
+        upper.iter().copied().collect::<std::vec::Vec<_>>(),
+        [6, 7, 8, 9, 10]
+    );
+    assert_eq!(tree.len(), 5);
+    assert_eq!(upper.len(), 5);
+    assert_eq!(upper.branching_factor(), tree.branching_factor());
+
+    let all = tree.split_off(&0);
+    assert!(tree.is_empty());
+    assert_eq!(all.len(), 5);
+}
+
+#[test]
+fn test_btree_set_append() {
+    let mut a = BTreeSet::new(2);
+    let mut b = BTreeSet::new(3);
+    for i in 1..=3 {
+        a.insert(i);
+    }
+    for i in 4..=6 {
+        b.insert(i);
+    }
+    a.append(&mut b);
+    assert_eq!(
+        a.iter().copied().collect::<std::vec::Vec<_>>(),
+        [1, 2, 3, 4, 5, 6]
+    );
+    assert_eq!(a.len(), 6);
+    assert!(b.is_empty());
+    assert_eq!(b.len(), 0);
+
+    b.insert(3);
+    b.insert(7);
+    a.append(&mut b);
+    assert_eq!(a.len(), 7);
+    assert!(b.is_empty());
+}
+
+#[test]
+fn test_btree_set_from_sorted() {
+    let data: std::vec::Vec<u32> = (0..1000).collect();
+    for bf in [2, 3, 10] {
+        let bulk = BTreeSet::from_sorted(&data, bf);
+        let mut inserted = BTreeSet::new(bf);
+        for &x in &data {
+            inserted.insert(x);
+        }
+
+        bulk.assert_invariants();
+        assert_eq!(bulk.len(), 1000);
+        assert!(bulk.iter().eq(inserted.iter()));
+        for x in &data {
+            assert!(bulk.contains(x));
+        }
+
+        // one by one insertion of sorted data leaves the nodes about half full, the bulk built
+        // tree is as shallow as possible and packs the nodes nearly full
+        // a full tree of height h holds degree^h - 1 keys
+        let degree = 2 * bf;
+        let min_height = (1..).find(|&h| degree.pow(h) > data.len()).unwrap() as usize;
+        assert_eq!(bulk.height(), min_height);
+        assert!(bulk.node_count() < inserted.node_count());
+        assert!(bulk.load_factor() > 0.9);
+        assert!(inserted.load_factor() < 0.5);
+        if bf <= 3 {
+            assert!(bulk.height() < inserted.height());
+        } else {
+            // with a large branching factor, both trees fit everything into three levels
+            assert!(bulk.height() <= inserted.height());
+        }
+
+        // the built tree must stay valid when it changes
+        let mut bulk = bulk;
+        for x in (0..1000).step_by(2) {
+            assert_eq!(bulk.remove(&x), Some(x));
+        }
+        bulk.insert(2000);
+        assert!(bulk.iter().copied().eq((1..1000).step_by(2).chain([2000])));
+        bulk.assert_invariants();
+    }
+
+    assert!(BTreeSet::<u32>::from_sorted(&[], 3).is_empty());
+    assert_eq!(BTreeSet::from_sorted(&[1, 2], 3).height(), 1);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_btree_set_serde_roundtrip() {
+    let mut tree = BTreeSet::new(2);
+    for x in [5, 1, 4, 2, 3] {
+        tree.insert(x);
+    }
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(json, "[1,2,3,4,5]");
+
+    let back: BTreeSet<u32> = serde_json::from_str(&json).unwrap();
+    assert!(back.iter().eq(tree.iter()));
+    assert_eq!(back.branching_factor(), DEFAULT_BRANCH_FACTOR);
+    assert!(serde_json::from_str::<BTreeSet<u32>>("{}").is_err());
+}
+
+#[test]
+fn test_btree_set_invariants() {
+    for bf in 2..5 {
+        let mut tree = BTreeSet::new(bf);
+        tree.assert_invariants();
+        let mut x: u64 = 42;
+        for _ in 0..3000 {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let v = (x >> 33) % 300;
+            if (x >> 20).is_multiple_of(3) {
+                tree.remove(&v);
+            } else {
+                tree.insert(v);
+            }
+            tree.assert_invariants();
+        }
+        while tree.pop_first().is_some() {
+            tree.assert_invariants();
+        }
+
+        let data: std::vec::Vec<u64> = (0..500).collect();
+        BTreeSet::from_sorted(&data, bf).assert_invariants();
+    }
+}
+
+#[test]
+fn test_btree_set_floor_ceil() {
+    let mut tree = BTreeSet::new(2);
+    for x in [1, 3, 5, 7] {
+        tree.insert(x);
+    }
+    assert_eq!(tree.floor(&4), Some(&3));
+    assert_eq!(tree.ceil(&4), Some(&5));
+    assert_eq!(tree.floor(&0), None);
+    assert_eq!(tree.ceil(&8), None);
+    assert_eq!(tree.floor(&5), Some(&5));
+    assert_eq!(tree.ceil(&5), Some(&5));
+
+    let mut tree = BTreeSet::new(2);
+    for x in (0..1000).step_by(10) {
+        tree.insert(x);
+    }
+    for q in 0..1000u32 {
+        assert_eq!(tree.floor(&q), Some(&(q / 10 * 10)));
+        let ceil = q.div_ceil(10) * 10;
+        assert_eq!(tree.ceil(&q), (ceil < 1000).then_some(&ceil));
+    }
+}
+
+#[test]
+fn test_btree_set_drain() {
+    let mut tree = BTreeSet::new(2);
+    for x in 1..=50 {
+        tree.insert(x);
+    }
+    let drained: std::vec::Vec<_> = tree.drain().collect();
+    assert_eq!(drained, (1..=50).collect::<std::vec::Vec<_>>());
+    assert!(tree.is_empty());
+    assert_eq!(tree.len(), 0);
+    assert_eq!(tree.node_count(), 1);
+
+    for x in 1..=50 {
+        tree.insert(x);
+    }
+    let first: std::vec::Vec<_> = tree.drain().take(3).collect();
+    assert_eq!(first, [1, 2, 3]);
+    assert!(tree.is_empty());
+    assert_eq!(tree.node_count(), 1);
+    assert_eq!(tree.branching_factor(), 2);
+    tree.assert_invariants();
+}
+
+#[test]
+fn test_btree_set_into_iter() {
+    let mut tree = BTreeSet::new(2);
+    for x in [5, 3, 9, 1, 7] {
+        tree.insert(x.to_string());
+    }
+    let mut owned = std::vec::Vec::new();
+    for x in tree {
+        owned.push(x);
+    }
+    assert_eq!(owned, ["1", "3", "5", "7", "9"]);
+
+    let mut tree = BTreeSet::new(2);
+    for x in 0..100 {
+        tree.insert(x);
+    }
+    let mut it = tree.into_iter();
+    assert_eq!(it.size_hint(), (100, Some(100)));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.size_hint(), (99, Some(99)));
+}
+
+#[test]
+fn test_btree_set_from_iter_extend() {
+    // 37 is coprime to 100, so this visits every number below 100 exactly once
+    let shuffled = (0..100u32).map(|i| (i * 37) % 100);
+    let tree: BTreeSet<u32> = shuffled.collect();
+    assert_eq!(tree.len(), 100);
+    assert_eq!(tree.branching_factor(), DEFAULT_BRANCH_FACTOR);
+    assert!(tree.iter().copied().eq(0..100));
+
+    let mut tree = BTreeSet::new(2);
+    tree.extend([3, 1, 2]);
+    tree.extend((0..10).rev());
+    assert!(tree.iter().copied().eq(0..10));
+    tree.assert_invariants();
+}
+
+#[test]
+fn test_btree_set_iter_rev() {
+    for bf in [2, 3, 10] {
+        let tree: BTreeSet<u32> = {
+            let mut t = BTreeSet::new(bf);
+            t.extend(1..=100);
+            t
+        };
+        let rev: std::vec::Vec<_> = tree.iter().rev().copied().collect();
+        assert_eq!(rev, (1..=100).rev().collect::<std::vec::Vec<_>>());
+
+        // alternate between both ends, every element must come up exactly once
+        let mut it = tree.iter();
+        let mut seen = std::vec::Vec::new();
+        loop {
+            match (it.next(), it.next_back()) {
+                (Some(a), Some(b)) => seen.extend([*a, *b]),
+                (Some(a), None) => seen.push(*a),
+                (None, Some(b)) => seen.push(*b),
+                (None, None) => break,
+            }
+        }
+        seen.sort();
+        assert_eq!(seen, (1..=100).collect::<std::vec::Vec<_>>());
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next_back(), None);
+    }
+
+    let empty = BTreeSet::<u32>::new(2);
+    assert_eq!(empty.iter().next_back(), None);
+
+    let mut one = BTreeSet::new(2);
+    one.insert(1);
+    let mut it = one.iter();
+    assert_eq!(it.next_back(), Some(&1));
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_btree_set_select() {
+    let tree: BTreeSet<u32> = (0..100).rev().collect();
+    assert_eq!(tree.select(42), Some(&42));
+    assert_eq!(tree.select(0), Some(&0));
+    assert_eq!(tree.select(99), Some(&99));
+    assert_eq!(tree.select(100), None);
+}
+
+#[test]
+fn test_btree_set_rank() {
+    let tree: BTreeSet<u32> = (0..100).collect();
+    assert_eq!(tree.rank(&42), 42);
+    assert_eq!(tree.rank(&0), 0);
+    assert_eq!(tree.rank(&1000), 100);
+    for n in [0, 17, 99] {
+        assert_eq!(tree.rank(tree.select(n).unwrap()), n);
+    }
+}
+
+#[test]
+fn test_btree_set_count_range() {
+    let tree: BTreeSet<u32> = (0..100).collect();
+    assert_eq!(tree.count_range(10..20), 10);
+    assert_eq!(tree.count_range(10..=20), 11);
+    assert_eq!(tree.count_range(..), 100);
+    assert_eq!(tree.count_range(95..), 5);
+    assert_eq!(tree.count_range(200..), 0);
+}
+
+#[test]
+fn test_btree_set_clear_keeps_degree() {
+    let mut tree = BTreeSet::new(3);
+    let degree = tree.props.degree;
+    for _ in 0..5 {
+        tree.extend(0..100);
+        tree.clear();
+        assert!(tree.is_empty());
+        assert_eq!(tree.props.degree, degree);
+        assert_eq!(tree.branching_factor(), 3);
+    }
+}
+
+#[test]
+fn test_btree_set_take_replace() {
+    let mut tree = BTreeSet::new(2);
+    for id in 0..20 {
+        assert!(tree.replace(Keyed::new(id, "old")).is_none());
+    }
+    assert_eq!(tree.len(), 20);
+
+    let old = tree.replace(Keyed::new(7, "new")).unwrap();
+    assert_eq!(old.payload, "old");
+    assert_eq!(tree.len(), 20);
+    assert_eq!(tree.get(&Keyed::new(7, "")).unwrap().payload, "new");
+
+    let taken = tree.take(&Keyed::new(7, "")).unwrap();
+    assert_eq!(taken.payload, "new");
+    assert_eq!(tree.len(), 19);
+    assert!(tree.take(&Keyed::new(7, "")).is_none());
+}
+
+#[test]
+fn test_btree_set_insert_counts_distinct() {
+    let mut tree = BTreeSet::new(2);
+    let values = (0..300u32).map(|i| i * 7 % 50);
+    let distinct = values.filter(|&v| tree.insert(v)).count();
+    assert_eq!(distinct, 50);
+    assert_eq!(tree.len(), 50);
+}
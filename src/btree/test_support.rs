@@ -0,0 +1,35 @@
+use std::cmp::Ordering;
+
+/// Test element that is ordered and compared by `id` only, so that equal elements can still be
+/// told apart by their `payload`
+#[derive(Debug, Clone)]
+pub(crate) struct Keyed {
+    pub(crate) id: u32,
+    pub(crate) payload: &'static str,
+}
+
+impl Keyed {
+    pub(crate) fn new(id: u32, payload: &'static str) -> Self {
+        Self { id, payload }
+    }
+}
+
+impl PartialEq for Keyed {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Keyed {}
+
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Keyed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
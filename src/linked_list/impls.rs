@@ -31,6 +31,16 @@ impl<T> Default for LinkedList<T> {
     }
 }
 
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        let mut new = Self::new();
+        for element in self.iter() {
+            new.push_back(element.clone());
+        }
+        new
+    }
+}
+
 impl<T: Debug> Debug for LinkedList<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_list().entries(self.iter()).finish()
@@ -50,12 +60,14 @@ impl<T: Debug> Debug for Node<T> {
 
 pub struct Iter<'a, T> {
     pub(crate) current: OpNodePtr<T>,
+    pub(crate) back: OpNodePtr<T>,
     pub(crate) remaining: usize,
     pub(crate) _phantom: std::marker::PhantomData<&'a T>,
 }
 
 pub struct IterMut<'a, T> {
     pub(crate) current: OpNodePtr<T>,
+    pub(crate) back: OpNodePtr<T>,
     pub(crate) remaining: usize,
     pub(crate) _phantom: std::marker::PhantomData<&'a mut T>,
 }
@@ -86,6 +98,22 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let back_ptr = self.back?;
+        let back_node = deref_node(back_ptr);
+
+        self.back = back_node.prev;
+        self.remaining -= 1;
+
+        Some(&back_node.value)
+    }
+}
+
 impl<T> IntoIterator for LinkedList<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
@@ -117,6 +145,22 @@ impl<'a, T> Iterator for IterMut<'a, T> {
     }
 }
 
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let back_ptr = self.back?;
+        let back_node = deref_node_mut(back_ptr);
+
+        self.back = back_node.prev;
+        self.remaining -= 1;
+
+        Some(&mut back_node.value)
+    }
+}
+
 impl<T> Iterator for IntoIter<T> {
     type Item = T;
 
@@ -129,5 +173,11 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
 unsafe impl<T: Send> Send for LinkedList<T> {}
 unsafe impl<T: Sync> Sync for LinkedList<T> {}
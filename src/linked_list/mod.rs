@@ -1,4 +1,5 @@
 use std::fmt::{Debug, Write};
+use std::mem;
 use std::ptr::NonNull;
 
 use impls::{Iter, IterMut};
@@ -129,6 +130,35 @@ impl<T> LinkedList<T> {
         }
     }
 
+    /// Inserts `element` at `index`, shifting all elements after it towards the back
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, element: T) {
+        assert!(
+            index <= self.len,
+            "insertion index (is {index}) should be <= len (is {})",
+            self.len
+        );
+
+        if index == 0 {
+            return self.push_front(element);
+        }
+        if index == self.len {
+            return self.push_back(element);
+        }
+
+        let p_next = self.find_node(index).expect("index was checked");
+        let p_prev = deref_node(p_next).prev.expect("node is not the head");
+        let p_node = self.create_node(element);
+
+        deref_node_mut(p_node).prev = Some(p_prev);
+        deref_node_mut(p_node).next = Some(p_next);
+        deref_node_mut(p_prev).next = Some(p_node);
+        deref_node_mut(p_next).prev = Some(p_node);
+    }
+
     /// Unlinks the head node and updates head pointer
     fn unlink_head(&mut self) -> NodePtr<T> {
         let head_ptr = self.head.expect("Cannot unlink head from empty list");
@@ -171,6 +201,23 @@ impl<T> LinkedList<T> {
         tail_ptr
     }
 
+    /// Unlinks any node from the list, updating head and tail pointers if needed
+    fn unlink_node(&mut self, node_ptr: NodePtr<T>) {
+        let node = deref_node_mut(node_ptr);
+
+        match node.prev {
+            Some(prev_ptr) => deref_node_mut(prev_ptr).next = node.next,
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next_ptr) => deref_node_mut(next_ptr).prev = node.prev,
+            None => self.tail = node.prev,
+        }
+
+        node.prev = None;
+        node.next = None;
+    }
+
     pub fn pop_front(&mut self) -> Option<T> {
         if self.is_empty() {
             return None;
@@ -191,6 +238,45 @@ impl<T> LinkedList<T> {
         Some(self.destroy_node(tail_ptr))
     }
 
+    /// Removes the element at `index` and returns it, or [`None`] if `index` is out of bounds
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        let node_ptr = self.find_node(index)?;
+        self.unlink_node(node_ptr);
+        Some(self.destroy_node(node_ptr))
+    }
+
+    /// Moves all elements of `other` to the end of this list, leaving `other` empty
+    ///
+    /// The nodes are relinked, nothing is allocated or copied.
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        let Some(other_head) = other.head.take() else {
+            return;
+        };
+        let other_tail = other.tail.take();
+
+        match self.tail {
+            None => self.head = Some(other_head),
+            Some(p_tail) => {
+                deref_node_mut(p_tail).next = Some(other_head);
+                deref_node_mut(other_head).prev = Some(p_tail);
+            }
+        }
+        self.tail = other_tail;
+        self.len += mem::take(&mut other.len);
+    }
+
+    /// Reverses the order of the elements in place, without allocating
+    pub fn reverse(&mut self) {
+        let mut current = self.head;
+        while let Some(p_node) = current {
+            let node = deref_node_mut(p_node);
+            mem::swap(&mut node.next, &mut node.prev);
+            // next and prev are swapped now, so `prev` leads further along the old order
+            current = node.prev;
+        }
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
     pub fn clear(&mut self) {
         while self.pop_front().is_some() {}
         debug_assert!(self.is_empty());
@@ -260,6 +346,7 @@ impl<T> LinkedList<T> {
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             current: self.head,
+            back: self.tail,
             remaining: self.len,
             _phantom: std::marker::PhantomData,
         }
@@ -269,12 +356,21 @@ impl<T> LinkedList<T> {
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             current: self.head,
+            back: self.tail,
             remaining: self.len,
             _phantom: std::marker::PhantomData,
         }
     }
 }
 
+impl<T: Clone> LinkedList<T> {
+    /// Clones all elements, in order, into a new [`Vec`](crate::vec::Vec)
+    #[must_use]
+    pub fn to_vec(&self) -> crate::vec::Vec<T> {
+        self.iter().cloned().collect()
+    }
+}
+
 impl<T: PartialEq> LinkedList<T> {
     #[must_use]
     pub fn contains(&self, element: &T) -> bool {
@@ -292,13 +388,7 @@ impl<T: PartialEq> LinkedList<T> {
 
             match current_node.next {
                 None => break,
-                Some(p_next) => {
-                    if p_next == self.head.unwrap() {
-                        // reached end
-                        break;
-                    };
-                    current_node = deref_node(p_next)
-                }
+                Some(p_next) => current_node = deref_node(p_next),
             }
         }
         false
@@ -320,13 +410,7 @@ impl<T: Debug> LinkedList<T> {
 
             match current_node.next {
                 None => break,
-                Some(p_next) => {
-                    if p_next == self.head.unwrap() {
-                        // reached end
-                        break;
-                    };
-                    current_node = deref_node(p_next)
-                }
+                Some(p_next) => current_node = deref_node(p_next),
             }
         }
         write!(&mut buf, "}}").unwrap();
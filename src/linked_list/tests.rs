@@ -147,3 +147,209 @@ fn test_ll_iter_into() {
         assert_eq!(li, 100 + i)
     }
 }
+
+#[test]
+fn test_ll_remove_at() {
+    let mut ll = LinkedList::new();
+    for i in 0..5 {
+        ll.push_back(i);
+    }
+    assert_eq!(ll.remove_at(2), Some(2));
+    assert_eq!(ll.iter().copied().collect::<Vec<_>>(), [0, 1, 3, 4]);
+    assert_eq!(ll.remove_at(0), Some(0));
+    assert_eq!(ll.iter().copied().collect::<Vec<_>>(), [1, 3, 4]);
+    assert_eq!(ll.remove_at(2), Some(4));
+    assert_eq!(ll.iter().copied().collect::<Vec<_>>(), [1, 3]);
+    assert_eq!(ll.first(), Some(&1));
+    assert_eq!(ll.last(), Some(&3));
+    assert_eq!(ll.len(), 2);
+
+    assert_eq!(ll.remove_at(2), None);
+    assert_eq!(ll.remove_at(0), Some(1));
+    assert_eq!(ll.remove_at(0), Some(3));
+    assert!(ll.is_empty());
+    assert_eq!(ll.first(), None);
+    assert_eq!(ll.last(), None);
+    assert_eq!(ll.remove_at(0), None);
+}
+
+#[test]
+fn test_ll_insert() {
+    let mut ll = LinkedList::new();
+    ll.insert(0, 2);
+    assert_eq!(ll.iter().copied().collect::<Vec<_>>(), [2]);
+    ll.insert(0, 0);
+    assert_eq!(ll.iter().copied().collect::<Vec<_>>(), [0, 2]);
+    ll.insert(1, 1);
+    assert_eq!(ll.iter().copied().collect::<Vec<_>>(), [0, 1, 2]);
+    ll.insert(3, 3);
+    assert_eq!(ll.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+    assert_eq!(ll.len(), 4);
+    assert_eq!(ll.first(), Some(&0));
+    assert_eq!(ll.last(), Some(&3));
+    // walk backwards to check the prev links
+    let mut back = Vec::new();
+    while let Some(v) = ll.pop_back() {
+        back.push(v);
+    }
+    assert_eq!(back, [3, 2, 1, 0]);
+}
+
+#[test]
+#[should_panic]
+fn test_ll_insert_out_of_bounds() {
+    let mut ll = LinkedList::new();
+    ll.push_back(0);
+    ll.insert(2, 1);
+}
+
+#[test]
+fn test_ll_append() {
+    let mut a = LinkedList::new();
+    a.push_back(1);
+    a.push_back(2);
+    let mut b = LinkedList::new();
+    b.push_back(3);
+    b.push_back(4);
+
+    a.append(&mut b);
+    assert_eq!(a.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    assert_eq!(a.len(), 4);
+    assert_eq!(a.last(), Some(&4));
+    assert!(b.is_empty());
+    assert_eq!(b.first(), None);
+    assert_eq!(b.last(), None);
+    assert_eq!(a.pop_back(), Some(4));
+    assert_eq!(a.pop_back(), Some(3));
+    assert_eq!(a.pop_back(), Some(2));
+
+    // appending an empty list does nothing
+    a.append(&mut b);
+    assert_eq!(a.iter().copied().collect::<Vec<_>>(), [1]);
+
+    // appending onto an empty list takes over the nodes
+    b.append(&mut a);
+    assert_eq!(b.iter().copied().collect::<Vec<_>>(), [1]);
+    assert_eq!(b.last(), Some(&1));
+    assert!(a.is_empty());
+}
+
+#[test]
+fn test_ll_iter_rev() {
+    let mut ll = LinkedList::new();
+    for i in 0..5 {
+        ll.push_back(i);
+    }
+    assert_eq!(
+        ll.iter().rev().copied().collect::<Vec<_>>(),
+        [4, 3, 2, 1, 0]
+    );
+
+    let mut it = ll.iter();
+    assert_eq!(it.next(), Some(&0));
+    assert_eq!(it.next_back(), Some(&4));
+    assert_eq!(it.next_back(), Some(&3));
+    assert_eq!(it.next(), Some(&1));
+    assert_eq!(it.next(), Some(&2));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next_back(), None);
+
+    for v in ll.iter_mut().rev().take(2) {
+        *v += 10;
+    }
+    let mut it = ll.iter_mut();
+    assert_eq!(it.next_back(), Some(&mut 14));
+    assert_eq!(it.next(), Some(&mut 0));
+    assert_eq!(it.next_back(), Some(&mut 13));
+    assert_eq!(it.size_hint(), (2, Some(2)));
+}
+
+#[test]
+fn test_ll_clone() {
+    let mut ll = LinkedList::new();
+    for s in ["foo", "bar", "qux"] {
+        ll.push_back(s.to_string());
+    }
+    let mut cloned = ll.clone();
+    assert_eq!(
+        ll.iter().collect::<Vec<_>>(),
+        cloned.iter().collect::<Vec<_>>()
+    );
+
+    cloned[1].push_str("baz");
+    cloned.push_back("new".to_string());
+    assert_eq!(ll.iter().collect::<Vec<_>>(), ["foo", "bar", "qux"]);
+    assert_eq!(
+        cloned.iter().collect::<Vec<_>>(),
+        ["foo", "barbaz", "qux", "new"]
+    );
+    assert_ne!(
+        ll.first_node().unwrap().as_ptr(),
+        cloned.first_node().unwrap().as_ptr()
+    );
+
+    drop(ll);
+    assert_eq!(cloned.len(), 4);
+}
+
+#[test]
+fn test_ll_contains_last() {
+    let mut ll = LinkedList::new();
+    assert!(!ll.contains(&0));
+    for i in 0..5 {
+        ll.push_back(i);
+    }
+    assert!(ll.contains(&0));
+    assert!(ll.contains(&4));
+    assert!(!ll.contains(&5));
+    assert_eq!(ll.debug_nodes().lines().count(), 7);
+}
+
+#[test]
+fn test_ll_into_iter_rev() {
+    let mut ll = LinkedList::new();
+    for i in 0..3 {
+        ll.push_back(i);
+    }
+    let mut it = ll.clone().into_iter();
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next_back(), Some(2));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next_back(), None);
+    assert_eq!(it.next(), None);
+
+    assert_eq!(ll.into_iter().rev().collect::<Vec<_>>(), [2, 1, 0]);
+}
+
+#[test]
+fn test_ll_reverse() {
+    let mut ll = LinkedList::new();
+    for i in 1..=4 {
+        ll.push_back(i);
+    }
+    ll.reverse();
+    assert_eq!(ll.len(), 4);
+    assert_eq!(ll.iter().copied().collect::<Vec<_>>(), [4, 3, 2, 1]);
+    assert_eq!(ll.iter().rev().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    for (i, v) in [4, 3, 2, 1].into_iter().enumerate() {
+        assert_eq!(ll.get(i), Some(&v));
+    }
+    ll.push_back(0);
+    assert_eq!(ll.pop_front(), Some(4));
+    assert_eq!(ll.iter().copied().collect::<Vec<_>>(), [3, 2, 1, 0]);
+
+    let mut empty: LinkedList<i32> = LinkedList::new();
+    empty.reverse();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_ll_to_vec() {
+    let mut ll = LinkedList::new();
+    for i in 0..5 {
+        ll.push_back(i);
+    }
+    assert_eq!(ll.to_vec(), crate::vec::Vec::from(&[0, 1, 2, 3, 4][..]));
+    assert_eq!(ll.len(), 5);
+    assert!(LinkedList::<i32>::new().to_vec().is_empty());
+}
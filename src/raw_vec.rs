@@ -1,11 +1,35 @@
 use std::{
     alloc::{self, Layout},
+    fmt::Display,
+    mem,
     ptr::NonNull,
 };
 
 use crate::trace_current_function;
 
-#[derive(Clone)]
+/// The error returned by fallible reservations like [`Vec::try_reserve`](crate::vec::Vec::try_reserve)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds the maximum of `isize::MAX` bytes
+    CapacityOverflow,
+    /// The allocator failed to provide the memory
+    AllocError { layout: Layout },
+}
+
+impl Display for TryReserveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CapacityOverflow => write!(f, "capacity overflow"),
+            Self::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TryReserveError {}
+
+// NOTE: RawVec must not be Clone, a copy of the pointer would lead to a double free
 pub(crate) struct RawVec<T> {
     pub(crate) ptr: NonNull<T>,
     pub(crate) capacity: usize,
@@ -13,30 +37,90 @@ pub(crate) struct RawVec<T> {
 
 impl<T> RawVec<T> {
     pub(crate) fn new() -> Self {
+        // ZSTs never need an allocation, so we can hold as many as we can count
+        let capacity = if mem::size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            0
+        };
         Self {
             ptr: NonNull::dangling(),
-            capacity: 0,
+            capacity,
         }
     }
 
-    // See rustonomicon, chapter 9.2
+    /// Takes ownership of an existing allocation
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated with the global allocator for exactly `capacity` elements
+    /// of `T`, or be dangling if `capacity` is 0 or `T` is zero sized.
+    pub(crate) unsafe fn from_raw_parts(ptr: NonNull<T>, capacity: usize) -> Self {
+        let capacity = if mem::size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            capacity
+        };
+        Self { ptr, capacity }
+    }
+
     pub(crate) fn grow_by(&mut self, added_capacity: usize) {
-        let new_cap = self.capacity + added_capacity;
+        let new_cap = self
+            .capacity
+            .checked_add(added_capacity)
+            .expect("capacity overflow");
+        self.grow_to(new_cap);
+    }
+
+    /// Grows the buffer so that it holds exactly `new_cap` elements
+    ///
+    /// Does nothing if the capacity is already at least `new_cap`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity would exceed `isize::MAX` bytes, and aborts if the allocation
+    /// fails.
+    pub(crate) fn grow_to(&mut self, new_cap: usize) {
+        match self.try_grow_to(new_cap) {
+            Ok(()) => (),
+            Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+            Err(TryReserveError::AllocError { layout }) => alloc::handle_alloc_error(layout),
+        }
+    }
+
+    /// Like [`grow_by`](Self::grow_by), but returns an error instead of panicking or aborting
+    pub(crate) fn try_grow_by(&mut self, added_capacity: usize) -> Result<(), TryReserveError> {
+        let new_cap = self
+            .capacity
+            .checked_add(added_capacity)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        self.try_grow_to(new_cap)
+    }
+
+    /// Like [`grow_to`](Self::grow_to), but returns an error instead of panicking or aborting
+    // See rustonomicon, chapter 9.2
+    pub(crate) fn try_grow_to(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+        // This also makes sure we never allocate zero bytes, which is undefined behavior
+        if new_cap <= self.capacity {
+            return Ok(());
+        }
+        // Since we set the capacity to usize::MAX when T has size 0,
+        // getting to here necessarily means the Vec is overfull.
+        if mem::size_of::<T>() == 0 {
+            return Err(TryReserveError::CapacityOverflow);
+        }
+
+        #[cfg(test)]
+        test::REALLOCATIONS.with(|c| c.set(c.get() + 1));
         trace_current_function!(
             "growing raw_vec at {:?} from {} to {}",
             self.ptr,
             self.capacity,
             new_cap
         );
-        // `Layout::array` checks that the number of bytes is <= usize::MAX,
-        // but this is redundant since old_layout.size() <= isize::MAX,
-        // so the `unwrap` should never fail.
-        let new_layout = Layout::array::<T>(new_cap).unwrap();
-
-        // Ensure that the new allocation doesn't exceed `isize::MAX` bytes.
-        if new_layout.size() > isize::MAX as usize {
-            alloc::handle_alloc_error(new_layout);
-        }
+        // `Layout::array` checks that the number of bytes is <= isize::MAX
+        let new_layout =
+            Layout::array::<T>(new_cap).map_err(|_| TryReserveError::CapacityOverflow)?;
 
         let new_ptr = if self.capacity == 0 {
             unsafe { alloc::alloc(new_layout) }
@@ -46,10 +130,10 @@ impl<T> RawVec<T> {
             unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) }
         };
 
-        // If allocation fails, `new_ptr` will be null, in which case we abort.
+        // If allocation fails, `new_ptr` will be null, the old allocation is still valid then
         self.ptr = match NonNull::new(new_ptr as *mut T) {
             Some(p) => p,
-            None => alloc::handle_alloc_error(new_layout),
+            None => return Err(TryReserveError::AllocError { layout: new_layout }),
         };
         self.capacity = new_cap;
         trace_current_function!(
@@ -57,11 +141,98 @@ impl<T> RawVec<T> {
             self.ptr,
             self.capacity
         );
+        Ok(())
+    }
+
+    /// Makes sure there is space for at least `additional` elements after the first `len`
+    ///
+    /// If the buffer needs to grow, its capacity is at least doubled for amortized O(1) growth.
+    /// Never shrinks the buffer.
+    pub(crate) fn reserve(&mut self, len: usize, additional: usize) {
+        match self.try_reserve(len, additional) {
+            Ok(()) => (),
+            Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+            Err(TryReserveError::AllocError { layout }) => alloc::handle_alloc_error(layout),
+        }
+    }
+
+    /// Like [`reserve`](Self::reserve), but grows to exactly `len + additional` if it needs to
+    /// grow at all
+    pub(crate) fn reserve_exact(&mut self, len: usize, additional: usize) {
+        if self.capacity - len >= additional {
+            return;
+        }
+        let required = len.checked_add(additional).expect("capacity overflow");
+        self.grow_to(required);
+    }
+
+    /// Like [`reserve`](Self::reserve), but returns an error instead of panicking or aborting
+    pub(crate) fn try_reserve(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        debug_assert!(len <= self.capacity);
+        if self.capacity - len >= additional {
+            return Ok(());
+        }
+        let required = len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        // can't overflow, a capacity of more than isize::MAX bytes is never allocated, and
+        // we never get here for ZSTs because their capacity is usize::MAX
+        let target = required.max(self.capacity * 2);
+        self.try_grow_by(target - self.capacity)
+    }
+
+    /// Reallocates the buffer so that it holds exactly `new_cap` elements
+    ///
+    /// Deallocates entirely if `new_cap` is 0. Does nothing if `new_cap` is not smaller than the
+    /// current capacity, or if `T` is zero sized.
+    pub(crate) fn shrink_to(&mut self, new_cap: usize) {
+        if mem::size_of::<T>() == 0 || new_cap >= self.capacity {
+            return;
+        }
+        trace_current_function!(
+            "shrinking raw_vec at {:?} from {} to {}",
+            self.ptr,
+            self.capacity,
+            new_cap
+        );
+        let old_layout = Layout::array::<T>(self.capacity).unwrap();
+        let old_ptr = self.ptr.as_ptr() as *mut u8;
+
+        if new_cap == 0 {
+            unsafe { alloc::dealloc(old_ptr, old_layout) };
+            self.ptr = NonNull::dangling();
+            self.capacity = 0;
+            return;
+        }
+
+        // Can't fail, the new layout is smaller than the old one
+        let new_layout = Layout::array::<T>(new_cap).unwrap();
+        let new_ptr = unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) };
+        self.ptr = match NonNull::new(new_ptr as *mut T) {
+            Some(p) => p,
+            None => alloc::handle_alloc_error(new_layout),
+        };
+        self.capacity = new_cap;
     }
 
+    /// Capacity of the first allocation when growing, so that small vectors don't reallocate on
+    /// every single push. Same heuristic as the standard library.
+    const MIN_NON_ZERO_CAP: usize = if mem::size_of::<T>() == 1 {
+        8
+    } else if mem::size_of::<T>() <= 1024 {
+        4
+    } else {
+        1
+    };
+
+    /// Grows the buffer geometrically, doubling the capacity
     pub(crate) fn grow(&mut self) {
         if self.capacity == 0 {
-            self.grow_by(1);
+            self.grow_to(Self::MIN_NON_ZERO_CAP);
         } else {
             self.grow_by(self.capacity);
         }
@@ -72,7 +243,7 @@ impl<T> Drop for RawVec<T> {
     fn drop(&mut self) {
         // NOTE: We need to free the allocated memory here,
         // otherwise there definitely is a memory leak.
-        if self.capacity != 0 {
+        if self.capacity != 0 && mem::size_of::<T>() != 0 {
             let layout = Layout::array::<T>(self.capacity).unwrap();
             unsafe {
                 alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout);
@@ -85,11 +256,16 @@ unsafe impl<T: Send> Send for RawVec<T> {}
 unsafe impl<T: Sync> Sync for RawVec<T> {}
 
 #[cfg(test)]
-mod test {
-    use std::ptr;
+pub(crate) mod test {
+    use std::{cell::Cell, ptr};
 
     use super::RawVec;
 
+    thread_local! {
+        /// How often a [`RawVec`] was grown on this thread
+        pub(crate) static REALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
     #[test]
     fn test_rawvec_alloc_dealloc() {
         let s = 2_000;
@@ -100,4 +276,77 @@ mod test {
         }
         drop(v)
     }
+
+    #[test]
+    fn test_rawvec_shrink() {
+        let mut v = RawVec::<u32>::new();
+        v.grow_by(100);
+        v.shrink_to(10);
+        assert_eq!(v.capacity, 10);
+        unsafe {
+            ptr::write_bytes(v.ptr.as_ptr(), b'A', 10);
+        }
+        v.shrink_to(20);
+        assert_eq!(v.capacity, 10);
+        v.shrink_to(0);
+        assert_eq!(v.capacity, 0);
+        drop(v)
+    }
+
+    #[test]
+    fn test_rawvec_grow_min_capacity() {
+        let mut v = crate::vec::Vec::<u32>::new();
+        for i in 0..3 {
+            v.push(i);
+        }
+        assert_eq!(v.capacity(), 4);
+        for i in 3..5 {
+            v.push(i);
+        }
+        assert_eq!(v.capacity(), 8);
+
+        let mut bytes = RawVec::<u8>::new();
+        bytes.grow();
+        assert_eq!(bytes.capacity, 8);
+    }
+
+    #[test]
+    fn test_rawvec_reserve() {
+        let mut v = RawVec::<u32>::new();
+        v.reserve(0, 10);
+        assert_eq!(v.capacity, 10);
+        v.reserve(8, 3);
+        assert_eq!(v.capacity, 20);
+        // enough space, must not shrink
+        v.reserve(0, 1);
+        assert_eq!(v.capacity, 20);
+        v.reserve(5, 100);
+        assert_eq!(v.capacity, 105);
+    }
+
+    #[test]
+    fn test_rawvec_reserve_exact() {
+        let mut v = RawVec::<u32>::new();
+        v.reserve_exact(0, 10);
+        assert_eq!(v.capacity, 10);
+        v.reserve_exact(8, 3);
+        assert_eq!(v.capacity, 11);
+        // enough space, must not shrink
+        v.reserve_exact(2, 2);
+        assert_eq!(v.capacity, 11);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_rawvec_reserve_overflow() {
+        let mut v = RawVec::<u32>::new();
+        v.reserve(0, usize::MAX);
+    }
+
+    #[test]
+    fn test_rawvec_zst() {
+        let v = RawVec::<()>::new();
+        assert_eq!(v.capacity, usize::MAX);
+        drop(v)
+    }
 }
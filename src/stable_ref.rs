@@ -96,7 +96,11 @@
 // moves in the memory
 #![allow(clippy::borrowed_box)]
 
-use std::{pin::Pin, ptr::NonNull};
+use std::{
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr::NonNull,
+};
 
 /// A reference that guarantees the pointed-to value has a stable memory address.
 ///
@@ -231,6 +235,11 @@ impl<'a, T> StableRef<'a, T> {
         Self::Ref(r)
     }
 
+    /// Creates a [`StableRef`] from a regular reference, storing it as a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`from_raw`](Self::from_raw).
     #[inline]
     pub unsafe fn from_ref_to_raw(r: &'a mut T) -> Self {
         unsafe { Self::from_raw(NonNull::new_unchecked(r as *mut T)) }
@@ -249,6 +258,37 @@ impl<'a, T> StableRef<'a, T> {
         }
     }
 
+    /// Projects the [StableRef] to a part of the referenced value, like one of its fields.
+    ///
+    /// The projected address is only stable if it lies in the same allocation as the original
+    /// value, so `f` should only ever return references into `T` itself, never into something
+    /// `T` points to, like the contents of a `Vec` field.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` unchanged if it is [`Boxed`](Self::Boxed), as the owned allocation would
+    /// be dropped while the projection still points into it.
+    ///
+    /// # Example
+    /// ```
+    /// use datastructurs::stable_ref::StableRef;
+    ///
+    /// let boxed = Box::new((1, 2));
+    /// let second = StableRef::from_boxref(&boxed).map(|pair| &pair.1).unwrap();
+    /// assert_eq!(*second, 2);
+    /// ```
+    #[inline]
+    pub fn map<U, F: FnOnce(&T) -> &U>(self, f: F) -> Result<StableRef<'a, U>, Self> {
+        match self {
+            // SAFETY: the caller of `from_raw` guaranteed that the pointer is valid for `'a`
+            Self::Raw(r) => Ok(StableRef::Raw(ref_to_raw(f(unsafe { r.as_ref() })))),
+            Self::BoxRef(r) => Ok(StableRef::Raw(ref_to_raw(f(r)))),
+            // the pinned value does not move for `'a`, so neither do its fields
+            Self::Ref(r) => Ok(StableRef::Raw(ref_to_raw(f(r.get_ref())))),
+            Self::Boxed(_) => Err(self),
+        }
+    }
+
     /// Convert the [StableRef] into a [StableRefMut]
     ///
     /// # Safety
@@ -359,6 +399,11 @@ impl<'a, T> StableRefMut<'a, T> {
         Self::BoxRef(r)
     }
 
+    /// Creates a [`StableRefMut`] from a regular reference, storing it as a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`from_raw`](Self::from_raw).
     #[inline]
     pub unsafe fn from_ref_to_raw(r: &'a mut T) -> Self {
         unsafe { Self::from_raw(NonNull::new_unchecked(r as *mut T)) }
@@ -377,6 +422,28 @@ impl<'a, T> StableRefMut<'a, T> {
         }
     }
 
+    /// Projects the [StableRefMut] to a part of the referenced value, like one of its fields.
+    ///
+    /// See [`StableRef::map`], the same restrictions apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` unchanged if it is [`Boxed`](Self::Boxed) or [`Ref`](Self::Ref). `f` gets
+    /// mutable access to the value, which could move it out of the [`Pin`] of a `Ref`.
+    #[inline]
+    pub fn map_mut<U, F: FnOnce(&mut T) -> &mut U>(
+        self,
+        f: F,
+    ) -> Result<StableRefMut<'a, U>, Self> {
+        match self {
+            // SAFETY: the caller of `from_raw` guaranteed that the pointer is valid for `'a` and
+            // not aliased
+            Self::Raw(mut r) => Ok(StableRefMut::Raw(NonNull::from(f(unsafe { r.as_mut() })))),
+            Self::BoxRef(r) => Ok(StableRefMut::Raw(NonNull::from(f(r)))),
+            Self::Ref(_) | Self::Boxed(_) => Err(self),
+        }
+    }
+
     /// Convert the [StableRefMut] into a [StableRef]
     ///
     /// # Safety
@@ -445,6 +512,38 @@ impl<'a, T> AsMut<T> for StableRefMut<'a, T> {
     }
 }
 
+impl<'a, T> Deref for StableRef<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.as_ref()
+    }
+}
+
+impl<'a, T> Deref for StableRefMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.as_ref()
+    }
+}
+
+/// Only for [`Unpin`] values, otherwise `*stable = other` could move a pinned value out of a
+/// [`Ref`](StableRefMut::Ref).
+///
+/// ```compile_fail
+/// use datastructurs::stable_ref::StableRefMut;
+/// use std::marker::PhantomPinned;
+///
+/// let mut stable = StableRefMut::create_box(PhantomPinned);
+/// *stable = PhantomPinned; // ERROR: PhantomPinned is not Unpin
+/// ```
+impl<'a, T: Unpin> DerefMut for StableRefMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.as_mut()
+    }
+}
+
 impl<T> From<Box<T>> for StableRef<'_, T> {
     fn from(value: Box<T>) -> Self {
         Self::Boxed(value)
@@ -539,6 +638,16 @@ mod tests {
         }
     }
 
+    impl Thing {
+        fn rename(&mut self, name: &str) {
+            self.b = name.to_string();
+        }
+
+        fn name_len(&self) -> usize {
+            self.b.len()
+        }
+    }
+
     fn inspect_thing(sref: StableRef<Thing>) {
         let thing = sref.as_ref();
         dbg!(thing);
@@ -597,6 +706,86 @@ mod tests {
         inspect_thing(rbox);
     }
 
+    #[test]
+    fn test_stable_ref_deref() {
+        let ref_thing = Box::new(Thing::new(3));
+        let rbox = StableRef::create_box(Thing::new(7));
+        let rref = StableRef::from_boxref(&ref_thing);
+
+        assert_eq!(rbox.name_len(), "Thing-7".len());
+        assert_eq!(rref.a, 3);
+        assert_eq!(*rbox, Thing::new(7));
+    }
+
+    #[test]
+    fn test_stable_ref_mut_deref() {
+        let raw_thing = Box::into_raw(Box::new(Thing::new(1)));
+        let mut rbox = StableRefMut::create_box(Thing::new(0));
+        let mut rraw = unsafe { StableRefMut::from_raw(NonNull::new(raw_thing).unwrap()) };
+
+        rbox.rename("boxed");
+        rraw.rename("raw");
+        rraw.a += 41;
+
+        assert_eq!(rbox.b, "boxed");
+        assert_eq!(rraw.name_len(), 3);
+        assert_eq!(rraw.a, 42);
+
+        drop(rraw);
+        drop(unsafe { Box::from_raw(raw_thing) });
+    }
+
+    #[test]
+    fn test_stable_ref_map() {
+        let pair = Box::new((1, 2));
+        let raw_pair = Box::into_raw(Box::new((3, 4)));
+
+        let second = StableRef::from_boxref(&pair).map(|p| &p.1).unwrap();
+        assert_eq!(*second, 2);
+        assert_eq!(second.as_ptr(), ref_to_raw(&pair.1));
+
+        let raw_second = unsafe { StableRef::from_raw(NonNull::new(raw_pair).unwrap()) }
+            .map(|p| &p.1)
+            .unwrap();
+        assert_eq!(*raw_second, 4);
+
+        let pinned = Box::pin((5, 6));
+        let pinned_second = StableRef::from_ref(pinned.as_ref()).map(|p| &p.1).unwrap();
+        assert_eq!(*pinned_second, 6);
+
+        drop(raw_second);
+        drop(unsafe { Box::from_raw(raw_pair) });
+    }
+
+    #[test]
+    fn test_stable_ref_map_mut() {
+        let mut thing = Box::new(Thing::new(5));
+        {
+            let mut a = StableRefMut::from_boxref(&mut thing)
+                .map_mut(|t| &mut t.a)
+                .unwrap();
+            *a *= 2;
+        }
+        assert_eq!(thing.a, 10);
+
+        let mut pinned = Box::pin(Thing::new(1));
+        assert!(
+            StableRefMut::from_ref(pinned.as_mut())
+                .map_mut(|t| &mut t.a)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_stable_ref_map_boxed() {
+        let stable = StableRef::create_box((1, 2));
+        let stable = stable.map(|p| &p.1).unwrap_err();
+        assert_eq!(*stable, (1, 2));
+
+        let stable = StableRefMut::create_box((1, 2));
+        assert!(stable.map_mut(|p| &mut p.1).is_err());
+    }
+
     // NOTE: This test does not panic but abort, the stable ref to value is constructed to be
     // invalid after the function finishes.
     #[test]
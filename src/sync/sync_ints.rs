@@ -1,55 +1,152 @@
-use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+use std::sync::atomic::{
+    AtomicI8, AtomicI16, AtomicI32, AtomicI64, AtomicIsize, AtomicU8, AtomicU16, AtomicU32,
+    AtomicU64, AtomicUsize, Ordering,
+};
 
 use crate::sync::SyncBox;
 
 macro_rules! atomic_syncbox_int {
-    ($name:ident, $primitive:ty) => {
+    ($name:ident, $primitive:ty, $atomic:ty) => {
+        /// Integer that can be shared across threads, clones refer to the same value
         #[derive(Debug, Clone, Default)]
         pub struct $name {
-            inner: SyncBox<$primitive>,
+            inner: SyncBox<$atomic>,
         }
 
         impl $name {
             #[inline(always)]
             pub fn new(value: $primitive) -> Self {
                 Self {
-                    inner: SyncBox::new(value),
+                    inner: SyncBox::new(<$atomic>::new(value)),
                 }
             }
 
             #[inline(always)]
             pub fn inc(&self) {
-                unsafe {
-                    (*self.inner.pointer()) += 1;
-                }
+                self.inner.get().fetch_add(1, Ordering::SeqCst);
+            }
+
+            #[inline(always)]
+            pub fn dec(&self) {
+                self.inner.get().fetch_sub(1, Ordering::SeqCst);
+            }
+
+            /// Adds `n`, wrapping around on overflow, and returns the previous value
+            #[inline(always)]
+            pub fn fetch_add(&self, n: $primitive) -> $primitive {
+                self.inner.get().fetch_add(n, Ordering::SeqCst)
+            }
+
+            /// Subtracts `n`, wrapping around on overflow, and returns the previous value
+            #[inline(always)]
+            pub fn fetch_sub(&self, n: $primitive) -> $primitive {
+                self.inner.get().fetch_sub(n, Ordering::SeqCst)
+            }
+
+            /// Stores `new` if the value currently is `current`
+            ///
+            /// Returns the previous value, as `Ok` if it was replaced and as `Err` otherwise.
+            #[inline(always)]
+            pub fn compare_exchange(
+                &self,
+                current: $primitive,
+                new: $primitive,
+            ) -> Result<$primitive, $primitive> {
+                self.inner
+                    .get()
+                    .compare_exchange(current, new, Ordering::SeqCst, Ordering::SeqCst)
             }
-        }
 
-        impl Deref for $name {
-            type Target = SyncBox<$primitive>;
+            #[inline(always)]
+            pub fn get(&self) -> $primitive {
+                self.inner.get().load(Ordering::SeqCst)
+            }
+
+            #[inline(always)]
+            pub fn set(&self, new: $primitive) {
+                self.inner.get().store(new, Ordering::SeqCst)
+            }
 
-            fn deref(&self) -> &Self::Target {
-                &self.inner
+            /// Same as [`get`](Self::get)
+            #[inline(always)]
+            pub fn val(&self) -> $primitive {
+                self.get()
             }
         }
+    };
+}
+
+/// Stand-in for the unstable 128 bit atomics, backed by a [`Mutex`]
+macro_rules! locked_int {
+    ($name:ident, $primitive:ty) => {
+        #[derive(Debug, Default)]
+        struct $name(Mutex<$primitive>);
+
+        impl $name {
+            fn new(value: $primitive) -> Self {
+                Self(Mutex::new(value))
+            }
+
+            fn lock(&self) -> std::sync::MutexGuard<'_, $primitive> {
+                // a panic can't leave the integer in an invalid state
+                self.0.lock().unwrap_or_else(|e| e.into_inner())
+            }
 
-        impl DerefMut for $name {
-            fn deref_mut(&mut self) -> &mut Self::Target {
-                &mut self.inner
+            fn load(&self, _order: Ordering) -> $primitive {
+                *self.lock()
+            }
+
+            fn store(&self, value: $primitive, _order: Ordering) {
+                *self.lock() = value;
+            }
+
+            fn fetch_add(&self, value: $primitive, _order: Ordering) -> $primitive {
+                let mut guard = self.lock();
+                let old = *guard;
+                *guard = old.wrapping_add(value);
+                old
+            }
+
+            fn fetch_sub(&self, value: $primitive, _order: Ordering) -> $primitive {
+                let mut guard = self.lock();
+                let old = *guard;
+                *guard = old.wrapping_sub(value);
+                old
+            }
+
+            fn compare_exchange(
+                &self,
+                current: $primitive,
+                new: $primitive,
+                _success: Ordering,
+                _failure: Ordering,
+            ) -> Result<$primitive, $primitive> {
+                let mut guard = self.lock();
+                let old = *guard;
+                if old == current {
+                    *guard = new;
+                    Ok(old)
+                } else {
+                    Err(old)
+                }
             }
         }
     };
 }
 
-atomic_syncbox_int!(SyncU128, u128);
-atomic_syncbox_int!(SyncUsize, usize);
-atomic_syncbox_int!(SyncU64, u64);
-atomic_syncbox_int!(SyncU32, u32);
-atomic_syncbox_int!(SyncU16, u16);
-atomic_syncbox_int!(SyncU8, u8);
-atomic_syncbox_int!(SyncI128, i128);
-atomic_syncbox_int!(SyncIsize, isize);
-atomic_syncbox_int!(SyncI64, i64);
-atomic_syncbox_int!(SyncI32, i32);
-atomic_syncbox_int!(SyncI16, i16);
-atomic_syncbox_int!(SyncI8, i8);
+locked_int!(LockedU128, u128);
+locked_int!(LockedI128, i128);
+
+atomic_syncbox_int!(SyncU128, u128, LockedU128);
+atomic_syncbox_int!(SyncUsize, usize, AtomicUsize);
+atomic_syncbox_int!(SyncU64, u64, AtomicU64);
+atomic_syncbox_int!(SyncU32, u32, AtomicU32);
+atomic_syncbox_int!(SyncU16, u16, AtomicU16);
+atomic_syncbox_int!(SyncU8, u8, AtomicU8);
+atomic_syncbox_int!(SyncI128, i128, LockedI128);
+atomic_syncbox_int!(SyncIsize, isize, AtomicIsize);
+atomic_syncbox_int!(SyncI64, i64, AtomicI64);
+atomic_syncbox_int!(SyncI32, i32, AtomicI32);
+atomic_syncbox_int!(SyncI16, i16, AtomicI16);
+atomic_syncbox_int!(SyncI8, i8, AtomicI8);
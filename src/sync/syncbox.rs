@@ -1,4 +1,6 @@
 use std::marker::PhantomData;
+use std::mem;
+use std::sync::atomic::{self, AtomicU32, Ordering};
 
 /// Allocates a value in a Box and makes it available with a raw pointer dereference across threads
 ///
@@ -12,17 +14,21 @@ pub struct SyncBox<T: Sized + Send + Sync> {
     pub(crate) dtype: PhantomData<T>,
 }
 
+// NOTE: repr(C) keeps `value` at offset 0, which `pointer` relies on
 #[derive(Debug)]
+#[repr(C)]
 struct SyncBoxInner<T: Sized + Send + Sync> {
-    #[allow(unused)]
     value: T,
-    refs: u32,
+    refs: AtomicU32,
 }
 
 impl<T: Sized + Send + Sync> SyncBox<T> {
     #[inline]
     pub fn new(value: T) -> Self {
-        let inner = SyncBoxInner { value, refs: 1 };
+        let inner = SyncBoxInner {
+            value,
+            refs: AtomicU32::new(1),
+        };
         let inner_box = Box::new(inner);
         Self {
             dtype: PhantomData,
@@ -52,6 +58,28 @@ impl<T: Sized + Send + Sync> SyncBox<T> {
     pub fn pointer(&self) -> *mut T {
         self.inner as *mut T
     }
+
+    /// Returns the inner value if this is the only [`SyncBox`] holding it, and gives back the
+    /// [`SyncBox`] otherwise
+    pub fn try_unwrap(self) -> Result<T, Self> {
+        let inner = self.inner;
+        if unsafe { &(*inner).refs }
+            .compare_exchange(1, 0, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(self);
+        }
+        // We just released the last reference, Drop must not free the allocation again
+        mem::forget(self);
+        let buf: Box<SyncBoxInner<T>> = unsafe { Box::from_raw(inner) };
+        Ok(buf.value)
+    }
+
+    /// How many [`SyncBox`]es currently share this value
+    #[inline]
+    pub fn strong_count(&self) -> u32 {
+        unsafe { (*self.inner).refs.load(Ordering::Acquire) }
+    }
 }
 
 impl<T: Copy + Sized + Send + Sync> SyncBox<T> {
@@ -69,7 +97,7 @@ impl<T: Sized + Send + Sync + Default> Default for SyncBox<T> {
 
 impl<T: Sized + Send + Sync> Clone for SyncBox<T> {
     fn clone(&self) -> Self {
-        unsafe { (*self.inner).refs += 1 };
+        unsafe { (*self.inner).refs.fetch_add(1, Ordering::Relaxed) };
         Self {
             dtype: self.dtype,
             inner: self.inner,
@@ -79,11 +107,14 @@ impl<T: Sized + Send + Sync> Clone for SyncBox<T> {
 
 impl<T: Sized + Send + Sync> Drop for SyncBox<T> {
     fn drop(&mut self) {
-        unsafe { (*self.inner).refs = (*self.inner).refs.saturating_sub(1) };
-        if unsafe { (*self.inner).refs < 1 } {
-            let buf: Box<_> = unsafe { Box::from_raw(self.inner) };
-            drop(buf)
+        // Same scheme as std's Arc: only the last owner frees, and the fence makes sure all
+        // uses by other owners happen before that
+        if unsafe { (*self.inner).refs.fetch_sub(1, Ordering::Release) } != 1 {
+            return;
         }
+        atomic::fence(Ordering::Acquire);
+        let buf: Box<_> = unsafe { Box::from_raw(self.inner) };
+        drop(buf)
     }
 }
 
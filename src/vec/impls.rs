@@ -1,5 +1,8 @@
 use std::fmt::Debug;
+use std::hash::Hash;
+use std::iter::FusedIterator;
 use std::marker::PhantomData;
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 
 use super::*;
 
@@ -16,18 +19,27 @@ pub struct IntoIter<T> {
 pub struct IntoIterRef<'a, T> {
     vec: &'a Vec<T>,
     index: usize,
+    /// One past the last element that was not yet yielded from the back
+    end: usize,
 }
 
 pub struct Drain<'a, T: 'a> {
     pub(crate) marker: PhantomData<&'a mut Vec<T>>,
+    pub(crate) vec: NonNull<Vec<T>>,
     pub(crate) iter: RawIter<T>,
+    /// Index of the first element after the drained range
+    pub(crate) tail_start: usize,
+    pub(crate) tail_len: usize,
 }
 
 impl<T> RawIter<T> {
     pub(crate) unsafe fn new(slice: &[T]) -> Self {
         RawIter {
             start: slice.as_ptr(),
-            end: if slice.is_empty() {
+            end: if mem::size_of::<T>() == 0 {
+                // For ZSTs the pointers are only used as counters
+                (slice.as_ptr() as usize + slice.len()) as *const T
+            } else if slice.is_empty() {
                 slice.as_ptr()
             } else {
                 unsafe { slice.as_ptr().add(slice.len()) }
@@ -36,6 +48,36 @@ impl<T> RawIter<T> {
     }
 }
 
+impl<T> RawIter<T> {
+    /// Pointer to the remaining elements, suitable for constructing a slice
+    fn remaining_ptr(&self) -> *mut T {
+        if mem::size_of::<T>() == 0 {
+            // for ZSTs, `start` is just a counter and might not be aligned
+            NonNull::dangling().as_ptr()
+        } else {
+            self.start as *mut T
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.size_hint().0
+    }
+}
+
+impl<T> IntoIter<T> {
+    /// Returns the elements that have not been yielded yet
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.iter.remaining_ptr(), self.iter.remaining()) }
+    }
+
+    /// Returns the elements that have not been yielded yet
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.iter.remaining_ptr(), self.iter.remaining()) }
+    }
+}
+
 impl<T> Iterator for RawIter<T> {
     type Item = T;
     fn next(&mut self) -> Option<T> {
@@ -43,15 +85,21 @@ impl<T> Iterator for RawIter<T> {
             None
         } else {
             unsafe {
-                let result = ptr::read(self.start);
-                self.start = self.start.offset(1);
-                Some(result)
+                if mem::size_of::<T>() == 0 {
+                    self.start = (self.start as usize + 1) as *const T;
+                    Some(ptr::read(NonNull::<T>::dangling().as_ptr()))
+                } else {
+                    let result = ptr::read(self.start);
+                    self.start = self.start.offset(1);
+                    Some(result)
+                }
             }
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = (self.end as usize - self.start as usize) / mem::size_of::<T>();
+        let elem_size = mem::size_of::<T>().max(1);
+        let len = (self.end as usize - self.start as usize) / elem_size;
         (len, Some(len))
     }
 }
@@ -62,13 +110,22 @@ impl<T> DoubleEndedIterator for RawIter<T> {
             None
         } else {
             unsafe {
-                self.end = self.end.offset(-1);
-                Some(ptr::read(self.end))
+                if mem::size_of::<T>() == 0 {
+                    self.end = (self.end as usize - 1) as *const T;
+                    Some(ptr::read(NonNull::<T>::dangling().as_ptr()))
+                } else {
+                    self.end = self.end.offset(-1);
+                    Some(ptr::read(self.end))
+                }
             }
         }
     }
 }
 
+impl<T> ExactSizeIterator for RawIter<T> {}
+
+impl<T> FusedIterator for RawIter<T> {}
+
 impl<T> Index<usize> for Vec<T> {
     type Output = T;
 
@@ -85,6 +142,35 @@ impl<T> IndexMut<usize> for Vec<T> {
     }
 }
 
+macro_rules! impl_range_index {
+    ($($range:ty),+ $(,)?) => {$(
+        impl<T> Index<$range> for Vec<T> {
+            type Output = [T];
+
+            #[inline]
+            fn index(&self, index: $range) -> &Self::Output {
+                Index::index(&**self, index)
+            }
+        }
+
+        impl<T> IndexMut<$range> for Vec<T> {
+            #[inline]
+            fn index_mut(&mut self, index: $range) -> &mut Self::Output {
+                IndexMut::index_mut(&mut **self, index)
+            }
+        }
+    )+};
+}
+
+impl_range_index!(
+    Range<usize>,
+    RangeTo<usize>,
+    RangeFrom<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeToInclusive<usize>,
+);
+
 impl<T> Deref for Vec<T> {
     type Target = [T];
     fn deref(&self) -> &[T] {
@@ -98,6 +184,28 @@ impl<T> DerefMut for Vec<T> {
     }
 }
 
+impl<T: Clone> Clone for Vec<T> {
+    fn clone(&self) -> Self {
+        let mut v = Self::with_capacity(self.capacity());
+        for item in self.iter() {
+            v.push(item.clone());
+        }
+        v
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        if self.capacity() < source.len() {
+            *self = source.clone();
+            return;
+        }
+        // reuse the existing allocation, pushing can't reallocate here
+        self.truncate(0);
+        for item in source.iter() {
+            self.push(item.clone());
+        }
+    }
+}
+
 impl<T> Drop for Vec<T> {
     fn drop(&mut self) {
         while self.pop().is_some() {}
@@ -110,6 +218,29 @@ impl<T> From<&[T]> for Vec<T> {
     }
 }
 
+impl<T> From<Box<[T]>> for Vec<T> {
+    fn from(value: Box<[T]>) -> Self {
+        let len = value.len();
+        let ptr = Box::into_raw(value) as *mut T;
+        // Box<[T]> uses the same layout as our buffer, so we can just take over the allocation
+        let buf = unsafe { RawVec::from_raw_parts(NonNull::new_unchecked(ptr), len) };
+        Vec { used: len, buf }
+    }
+}
+
+impl<T> From<std::vec::Vec<T>> for Vec<T> {
+    fn from(value: std::vec::Vec<T>) -> Self {
+        // Goes through a boxed slice so the allocation is handed over, nothing is cloned
+        Self::from(value.into_boxed_slice())
+    }
+}
+
+impl<T> From<Vec<T>> for std::vec::Vec<T> {
+    fn from(value: Vec<T>) -> Self {
+        value.into_boxed_slice().into_vec()
+    }
+}
+
 impl<T: PartialEq> PartialEq for Vec<T> {
     fn eq(&self, other: &Self) -> bool {
         if self.len() != other.len() {
@@ -126,6 +257,43 @@ impl<T: PartialEq> PartialEq for Vec<T> {
 
 impl<T: Eq> Eq for Vec<T> {}
 
+impl<T: PartialEq> PartialEq<[T]> for Vec<T> {
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: PartialEq> PartialEq<&[T]> for Vec<T> {
+    fn eq(&self, other: &&[T]) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq<[T; N]> for Vec<T> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        **self == other[..]
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Vec<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord> Ord for Vec<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<T: Hash> Hash for Vec<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // same as the slice, which hashes the length and then each element
+        Hash::hash(&**self, state)
+    }
+}
+
 impl<T> Iterator for IntoIter<T> {
     type Item = T;
 
@@ -143,11 +311,15 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
     }
 }
 
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
 impl<'a, T> Iterator for IntoIterRef<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.vec.len() {
+        if self.index < self.end {
             let item = &self.vec[self.index];
             self.index += 1;
             Some(item)
@@ -155,8 +327,28 @@ impl<'a, T> Iterator for IntoIterRef<'a, T> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIterRef<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index < self.end {
+            self.end -= 1;
+            Some(&self.vec[self.end])
+        } else {
+            None
+        }
+    }
 }
 
+impl<T> ExactSizeIterator for IntoIterRef<'_, T> {}
+
+impl<T> FusedIterator for IntoIterRef<'_, T> {}
+
 impl<T> IntoIterator for Vec<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
@@ -193,9 +385,32 @@ impl<'a, T> DoubleEndedIterator for Drain<'a, T> {
     }
 }
 
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
 impl<'a, T> Drop for Drain<'a, T> {
     fn drop(&mut self) {
-        for _ in &mut *self {}
+        /// Moves the tail back even if dropping one of the remaining elements panics
+        struct TailGuard<'r, 'a, T>(&'r mut Drain<'a, T>);
+
+        impl<T> Drop for TailGuard<'_, '_, T> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let vec = unsafe { drain.vec.as_mut() };
+                let start = vec.len();
+                if drain.tail_len > 0 && drain.tail_start != start {
+                    unsafe {
+                        let base = vec.as_mut_ptr();
+                        ptr::copy(base.add(drain.tail_start), base.add(start), drain.tail_len);
+                    }
+                }
+                unsafe { vec.set_len(start + drain.tail_len) };
+            }
+        }
+
+        let guard = TailGuard(self);
+        for _ in &mut guard.0.iter {}
     }
 }
 
@@ -207,22 +422,32 @@ impl<'a, T> IntoIterator for &'a Vec<T> {
         IntoIterRef {
             vec: self,
             index: 0,
+            end: self.len(),
         }
     }
 }
 
+impl<'a, T> IntoIterator for &'a mut Vec<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
+}
+
 impl<T> FromIterator<T> for Vec<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut vec = Vec::new();
-        for item in iter {
-            vec.push(item);
-        }
+        vec.extend(iter);
         vec
     }
 }
 
 impl<T> Extend<T> for Vec<T> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
         for item in iter {
             self.push(item);
         }
@@ -237,3 +462,44 @@ impl<T: Debug> Debug for Vec<T> {
 
 unsafe impl<T: Send> Send for Vec<T> {}
 unsafe impl<T: Sync> Sync for Vec<T> {}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::marker::PhantomData;
+
+    use serde::de::{SeqAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Vec;
+
+    impl<T: Serialize> Serialize for Vec<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    struct VecVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for VecVisitor<T> {
+        type Value = Vec<T>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a sequence")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            // don't trust the size hint too much, it comes from the input
+            let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            while let Some(item) = seq.next_element()? {
+                v.push(item);
+            }
+            Ok(v)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for Vec<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_seq(VecVisitor(PhantomData))
+        }
+    }
+}
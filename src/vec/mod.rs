@@ -4,18 +4,19 @@
 //! https://doc.rust-lang.org/nomicon/vec/vec.html
 
 use std::{
-    mem,
-    ops::{Deref, DerefMut, Index, IndexMut},
-    ptr,
+    cmp::Ordering,
+    mem::{self, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds},
+    ptr::{self, NonNull},
 };
 
 use impls::{Drain, RawIter};
 
 use crate::raw_vec::RawVec;
+pub use crate::raw_vec::TryReserveError;
 
 mod impls;
 
-#[derive(Clone)]
 pub struct Vec<T> {
     used: usize,
     buf: RawVec<T>,
@@ -29,9 +30,6 @@ impl<T> Default for Vec<T> {
 
 impl<T> Vec<T> {
     pub fn new() -> Self {
-        if mem::size_of::<T>() == 0 {
-            panic!("We're not ready to handle ZSTs");
-        }
         Vec {
             used: 0,
             buf: RawVec::new(),
@@ -39,10 +37,6 @@ impl<T> Vec<T> {
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
-        if mem::size_of::<T>() == 0 {
-            panic!("We're not ready to handle ZSTs");
-        }
-
         let mut v = Self::new();
         v.reserve(capacity);
         v
@@ -57,13 +51,17 @@ impl<T> Vec<T> {
         v
     }
 
+    /// Removes and drops all elements, keeping the allocated capacity
     pub fn clear(&mut self) {
-        *self = Self::new();
+        self.truncate(0);
     }
 
     pub fn pop(&mut self) -> Option<T> {
         if self.used == 0 {
             None
+        } else if Self::is_zst() {
+            self.used -= 1;
+            unsafe { Some(ptr::read(NonNull::dangling().as_ptr())) }
         } else {
             self.used -= 1;
             unsafe { Some(ptr::read(self.buf.ptr.as_ptr().add(self.used))) }
@@ -71,6 +69,12 @@ impl<T> Vec<T> {
     }
 
     pub fn push(&mut self, value: T) {
+        if Self::is_zst() {
+            // Nothing to store, the value is conjured up again when it is taken out
+            mem::forget(value);
+            self.used = self.used.checked_add(1).expect("capacity overflow");
+            return;
+        }
         if self.used == self.buf.capacity {
             self.buf.grow();
         }
@@ -96,10 +100,51 @@ impl<T> Vec<T> {
         self.len() == 0
     }
 
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.used {
+            return None;
+        }
+        Some(&self.as_slice()[index])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.used {
+            return None;
+        }
+        Some(&mut self.as_mut_slice()[index])
+    }
+
+    #[must_use]
+    pub fn first(&self) -> Option<&T> {
+        self.as_slice().first()
+    }
+
+    #[must_use]
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.as_mut_slice().first_mut()
+    }
+
+    #[must_use]
+    pub fn last(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    #[must_use]
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.as_mut_slice().last_mut()
+    }
+
     pub fn insert(&mut self, index: usize, elem: T) {
         // Note: `<=` because it's valid to insert after everything
         // which would be equivalent to push.
         assert!(index <= self.used, "index out of bounds");
+        if Self::is_zst() {
+            mem::forget(elem);
+            self.used = self.used.checked_add(1).expect("capacity overflow");
+            return;
+        }
         if self.used == self.buf.capacity {
             self.buf.grow();
         }
@@ -122,6 +167,10 @@ impl<T> Vec<T> {
         if index >= self.used {
             return None;
         }
+        if Self::is_zst() {
+            self.used -= 1;
+            return unsafe { Some(ptr::read(NonNull::dangling().as_ptr())) };
+        }
         unsafe {
             self.used -= 1;
             let result = ptr::read(self.buf.ptr.as_ptr().add(index));
@@ -134,8 +183,214 @@ impl<T> Vec<T> {
         }
     }
 
-    pub fn reserve(&mut self, added_capacity: usize) {
-        self.buf.grow_by(added_capacity);
+    /// Shortens the vector to `len` elements, dropping the rest
+    ///
+    /// Does nothing if `len` is greater than the current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.used {
+            return;
+        }
+        let excess = self.used - len;
+        unsafe {
+            // set the length first, so that a panicking drop can't lead to a double drop
+            self.set_len(len);
+            let tail = ptr::slice_from_raw_parts_mut(self.as_mut_ptr().add(len), excess);
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Resizes the vector to `new_len`, filling new slots with values generated by `f`
+    pub fn resize_with<F: FnMut() -> T>(&mut self, new_len: usize, mut f: F) {
+        if new_len <= self.used {
+            self.truncate(new_len);
+            return;
+        }
+        self.reserve(new_len - self.used);
+        while self.used < new_len {
+            self.push(f());
+        }
+    }
+
+    /// Removes the element at `index` in O(1) by moving the last element into its place
+    ///
+    /// This does not preserve the order of the elements, use [`remove`](Self::remove) for that.
+    pub fn swap_remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.used {
+            return None;
+        }
+        self.used -= 1;
+        unsafe {
+            let base = self.buf.ptr.as_ptr();
+            let result = ptr::read(base.add(index));
+            // `index` may be the last element, so the regions might overlap
+            ptr::copy(base.add(self.used), base.add(index), 1);
+            Some(result)
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more elements
+    ///
+    /// May reserve more than requested to avoid frequent reallocations, the capacity is at least
+    /// doubled if it needs to grow at all.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buf.reserve(self.used, additional);
+    }
+
+    /// Reserves capacity for exactly `additional` more elements
+    ///
+    /// Prefer [`reserve`](Self::reserve) if more elements are likely to be added later.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.buf.reserve_exact(self.used, additional);
+    }
+
+    /// Like [`reserve`](Self::reserve), but returns an error instead of panicking or aborting if
+    /// the capacity can't be reserved
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf.try_reserve(self.used, additional)
+    }
+
+    /// Shrinks the capacity of the vector to its length, deallocating if it is empty
+    pub fn shrink_to_fit(&mut self) {
+        self.buf.shrink_to(self.used);
+    }
+
+    /// Converts the vector into a [`Box<[T]>`](Box), dropping any spare capacity
+    #[must_use]
+    pub fn into_boxed_slice(mut self) -> Box<[T]> {
+        self.shrink_to_fit();
+        let slice = ptr::slice_from_raw_parts_mut(self.as_mut_ptr(), self.used);
+        // The box takes over the allocation
+        mem::forget(self);
+        unsafe { Box::from_raw(slice) }
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, preserving their order.
+    ///
+    /// If `f` panics, the elements that were not yet looked at are kept and nothing is
+    /// dropped twice.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let original_len = self.used;
+        let mut guard = FilterGuard::new(self);
+
+        while guard.processed < original_len {
+            let current = unsafe { &mut *guard.vec.as_mut_ptr().add(guard.processed) };
+            if !f(current) {
+                guard.processed += 1;
+                guard.deleted += 1;
+                unsafe { ptr::drop_in_place(current) };
+                continue;
+            }
+            if guard.deleted > 0 {
+                unsafe {
+                    let hole = guard.vec.as_mut_ptr().add(guard.processed - guard.deleted);
+                    ptr::copy_nonoverlapping(current, hole, 1);
+                }
+            }
+            guard.processed += 1;
+        }
+    }
+
+    /// Removes consecutive elements for which `same_bucket` returns `true`, keeping the first
+    ///
+    /// `same_bucket` gets the element in question first and the last kept element second.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        let original_len = self.used;
+        if original_len <= 1 {
+            return;
+        }
+        let mut guard = FilterGuard::new(self);
+        // the first element is always kept
+        guard.processed = 1;
+
+        while guard.processed < original_len {
+            let base = guard.vec.as_mut_ptr();
+            let current = unsafe { &mut *base.add(guard.processed) };
+            let last_kept = unsafe { &mut *base.add(guard.processed - guard.deleted - 1) };
+            if same_bucket(current, last_kept) {
+                guard.processed += 1;
+                guard.deleted += 1;
+                unsafe { ptr::drop_in_place(current) };
+                continue;
+            }
+            if guard.deleted > 0 {
+                unsafe {
+                    let hole = base.add(guard.processed - guard.deleted);
+                    ptr::copy_nonoverlapping(current, hole, 1);
+                }
+            }
+            guard.processed += 1;
+        }
+    }
+
+    /// Removes consecutive elements that map to the same key, keeping the first
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Binary searches the sorted vector with a comparator function
+    ///
+    /// See [`slice::binary_search_by`] for details.
+    pub fn binary_search_by<F: FnMut(&T) -> Ordering>(&self, f: F) -> Result<usize, usize> {
+        self.as_slice().binary_search_by(f)
+    }
+
+    /// Sorts the vector in place with a comparator function, preserving the order of equal
+    /// elements
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, f: F) {
+        self.as_mut_slice().sort_by(f)
+    }
+
+    /// Divides the vector into two slices at `mid`, the first one holding the elements before
+    /// `mid`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is greater than the length.
+    #[must_use]
+    pub fn split_at(&self, mid: usize) -> (&[T], &[T]) {
+        assert!(mid <= self.used, "index out of bounds");
+        self.as_slice().split_at(mid)
+    }
+
+    /// Mutable version of [`split_at`](Self::split_at)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is greater than the length.
+    #[must_use]
+    pub fn split_at_mut(&mut self, mid: usize) -> (&mut [T], &mut [T]) {
+        assert!(mid <= self.used, "index out of bounds");
+        self.as_mut_slice().split_at_mut(mid)
+    }
+
+    /// Swaps the elements at `a` and `b`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        assert!(a < self.used && b < self.used, "index out of bounds");
+        self.as_mut_slice().swap(a, b)
+    }
+
+    /// Rotates the vector in place so that the element at `mid` becomes the first
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is greater than the length.
+    pub fn rotate_left(&mut self, mid: usize) {
+        assert!(mid <= self.used, "index out of bounds");
+        self.as_mut_slice().rotate_left(mid)
+    }
+
+    /// Rotates the vector in place so that the last `k` elements come first
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is greater than the length.
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.used, "index out of bounds");
+        self.as_mut_slice().rotate_right(k)
     }
 
     #[must_use]
@@ -150,10 +405,35 @@ impl<T> Vec<T> {
         other
     }
 
-    unsafe fn set_len(&mut self, new_length: usize) {
+    #[inline]
+    const fn is_zst() -> bool {
+        mem::size_of::<T>() == 0
+    }
+
+    /// Sets the length of the vector without touching the elements
+    ///
+    /// # Safety
+    ///
+    /// `new_length` must not exceed the capacity, and the elements up to `new_length` must be
+    /// initialized, for example through [`spare_capacity_mut`](Self::spare_capacity_mut).
+    pub unsafe fn set_len(&mut self, new_length: usize) {
+        debug_assert!(new_length <= self.capacity());
         self.used = new_length
     }
 
+    /// Returns the unused capacity of the vector as uninitialized memory
+    ///
+    /// After writing to it, [`set_len`](Self::set_len) can be used to mark the elements as
+    /// initialized.
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.as_mut_ptr().add(self.used) as *mut MaybeUninit<T>,
+                self.buf.capacity - self.used,
+            )
+        }
+    }
+
     #[must_use]
     pub const fn as_ptr(&self) -> *const T {
         self.buf.ptr.as_ptr()
@@ -164,24 +444,102 @@ impl<T> Vec<T> {
         self.buf.ptr.as_ptr()
     }
 
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
     pub fn drain_all(&mut self) -> Drain<'_, T> {
-        let iter = unsafe { RawIter::new(self) };
+        self.drain(..)
+    }
 
-        self.used = 0;
+    /// Removes the elements in `range` from the vector, returning them as an iterator
+    ///
+    /// The elements after the range are moved back when the iterator is dropped, elements of
+    /// the range that were not consumed are dropped then too.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its start is after its end.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let len = self.used;
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("range out of bounds"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("range out of bounds"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(start <= end, "range start is after range end");
+        assert!(end <= len, "range out of bounds");
+
+        let iter = unsafe { RawIter::new(&self[start..end]) };
+
+        // Everything from start on is owned by the Drain until it is dropped
+        self.used = start;
 
         Drain {
+            vec: NonNull::from(&mut *self),
             iter,
+            tail_start: end,
+            tail_len: len - end,
             marker: std::marker::PhantomData,
         }
     }
 }
 
+impl<T: Ord> Vec<T> {
+    /// Sorts the vector in place, preserving the order of equal elements
+    pub fn sort(&mut self) {
+        self.as_mut_slice().sort()
+    }
+
+    /// Binary searches the sorted vector for `x`
+    ///
+    /// Returns [`Ok`] with the index of a matching element, or [`Err`] with the index where `x`
+    /// could be inserted while keeping the order.
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize> {
+        self.as_slice().binary_search(x)
+    }
+}
+
+impl<T: PartialEq> Vec<T> {
+    #[must_use]
+    pub fn contains(&self, needle: &T) -> bool {
+        self.iter().any(|item| item == needle)
+    }
+
+    /// Removes consecutive repeated elements
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b)
+    }
+}
+
 impl<T: Clone> Vec<T> {
     pub fn from_elem(value: T, n: usize) -> Self {
         let mut v = Vec::with_capacity(n);
         v.extend_with(value, n);
         v
     }
+    /// Resizes the vector to `new_len`, filling new slots with clones of `value`
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        if new_len <= self.used {
+            self.truncate(new_len);
+        } else {
+            let n = new_len - self.used;
+            self.reserve(n);
+            self.extend_with(value, n);
+        }
+    }
+
     pub fn extend_with(&mut self, value: T, n: usize) {
         // PERF: primitive implementation
         for _ in 0..n {
@@ -190,6 +548,46 @@ impl<T: Clone> Vec<T> {
     }
 }
 
+/// Shared state of in-place filtering operations like [`Vec::retain`] and [`Vec::dedup_by`]
+///
+/// While the guard lives, the [`Vec`] claims to be empty, so a panic in user code can never lead
+/// to elements being dropped twice. On drop, the gap left by deleted elements is closed, which
+/// also covers the unprocessed tail if we are unwinding.
+struct FilterGuard<'a, T> {
+    vec: &'a mut Vec<T>,
+    processed: usize,
+    deleted: usize,
+    original_len: usize,
+}
+
+impl<'a, T> FilterGuard<'a, T> {
+    fn new(vec: &'a mut Vec<T>) -> Self {
+        let original_len = vec.used;
+        unsafe { vec.set_len(0) };
+        Self {
+            vec,
+            processed: 0,
+            deleted: 0,
+            original_len,
+        }
+    }
+}
+
+impl<T> Drop for FilterGuard<'_, T> {
+    fn drop(&mut self) {
+        if self.deleted > 0 {
+            unsafe {
+                ptr::copy(
+                    self.vec.as_ptr().add(self.processed),
+                    self.vec.as_mut_ptr().add(self.processed - self.deleted),
+                    self.original_len - self.processed,
+                );
+            }
+        }
+        unsafe { self.vec.set_len(self.original_len - self.deleted) }
+    }
+}
+
 #[macro_export]
 macro_rules! vec {
     () => {
@@ -257,3 +257,725 @@ fn test_vec_macro() {
     let b = vec![1; 6];
     assert_eq!(a, b)
 }
+
+#[test]
+fn test_vec_zst_pushpop() {
+    let mut v = Vec::new();
+    assert_eq!(v.capacity(), usize::MAX);
+
+    for _ in 0..5_000 {
+        v.push(());
+    }
+    assert_eq!(v.len(), 5_000);
+    v.insert(20, ());
+    assert_eq!(v.remove(0), Some(()));
+
+    for _ in 0..5_000 {
+        assert_eq!(v.pop(), Some(()));
+    }
+    assert_eq!(v.pop(), None);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn test_vec_zst_iter() {
+    let mut v = Vec::with_capacity(10);
+    for _ in 0..3_000 {
+        v.push(());
+    }
+
+    assert_eq!((&v).into_iter().count(), 3_000);
+    assert_eq!(v.iter().count(), 3_000);
+    assert_eq!(v.drain_all().count(), 3_000);
+    assert!(v.is_empty());
+
+    for _ in 0..3_000 {
+        v.push(());
+    }
+    let mut iter = v.into_iter();
+    assert_eq!(iter.size_hint(), (3_000, Some(3_000)));
+    assert_eq!(iter.next_back(), Some(()));
+    assert_eq!(iter.count(), 2_999);
+}
+
+#[test]
+fn test_vec_retain() {
+    let mut v = Vec::from(&[1, 2, 3, 4, 5, 6, 7, 8][..]);
+    v.retain(|x| x % 2 == 0);
+
+    assert_eq!(v.len(), 4);
+    assert_eq!(v, Vec::from(&[2, 4, 6, 8][..]));
+
+    v.retain(|_| false);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn test_vec_retain_panic() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut v = Vec::new();
+    for _ in 0..6 {
+        v.push(counter.clone());
+    }
+
+    let mut seen = 0;
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        v.retain(|_| {
+            seen += 1;
+            if seen == 4 {
+                panic!("predicate panicked");
+            }
+            seen % 2 == 0
+        })
+    }));
+    assert!(result.is_err());
+
+    // two were removed before the panic, everything else is still there
+    assert_eq!(v.len(), 4);
+    assert_eq!(Rc::strong_count(&counter), 5);
+    drop(v);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn test_vec_dedup() {
+    let mut v = Vec::from(&[1, 1, 2, 3, 3, 3, 1, 4, 4][..]);
+    v.dedup();
+    assert_eq!(v, Vec::from(&[1, 2, 3, 1, 4][..]));
+}
+
+#[test]
+fn test_vec_dedup_by_key() {
+    let mut v = Vec::from(&[(1, "a"), (1, "b"), (2, "c"), (3, "d"), (3, "e"), (1, "f")][..]);
+    v.dedup_by_key(|pair| pair.0);
+
+    assert_eq!(v.len(), 4);
+    assert_eq!(v, Vec::from(&[(1, "a"), (2, "c"), (3, "d"), (1, "f")][..]));
+
+    let mut strings: Vec<String> = ["aa", "ab", "b", "ba", "c"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    strings.dedup_by_key(|s| s.chars().next());
+    assert_eq!(strings.len(), 3);
+    assert_eq!(strings[1], "b");
+}
+
+#[test]
+fn test_vec_swap_remove() {
+    let mut v = Vec::from(&[1, 2, 3, 4, 5][..]);
+
+    assert_eq!(v.swap_remove(1), Some(2));
+    assert_eq!(v, Vec::from(&[1, 5, 3, 4][..]));
+
+    // removing the last element must not move anything around
+    assert_eq!(v.swap_remove(3), Some(4));
+    assert_eq!(v, Vec::from(&[1, 5, 3][..]));
+
+    assert_eq!(v.swap_remove(3), None);
+    assert_eq!(v.swap_remove(0), Some(1));
+    assert_eq!(v.swap_remove(0), Some(3));
+    assert_eq!(v.swap_remove(0), Some(5));
+    assert!(v.is_empty());
+    assert_eq!(v.swap_remove(0), None);
+}
+
+#[test]
+fn test_vec_resize() {
+    let mut v = Vec::from(&[1, 2][..]);
+    v.resize(5, 0);
+    assert_eq!(v, Vec::from(&[1, 2, 0, 0, 0][..]));
+
+    let mut v = Vec::from(&[1, 2][..]);
+    v.resize(1, 0);
+    assert_eq!(v, Vec::from(&[1][..]));
+}
+
+#[test]
+fn test_vec_resize_with() {
+    let mut counter = 2;
+    let mut v = Vec::from(&[1, 2][..]);
+    v.resize_with(5, || {
+        counter += 1;
+        counter
+    });
+    assert_eq!(v, Vec::from(&[1, 2, 3, 4, 5][..]));
+
+    v.resize_with(1, || unreachable!());
+    assert_eq!(v, Vec::from(&[1][..]));
+}
+
+#[test]
+fn test_vec_truncate() {
+    let mut v: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    v.truncate(10);
+    assert_eq!(v.len(), 3);
+    v.truncate(1);
+    assert_eq!(v.len(), 1);
+    assert_eq!(v[0], "a");
+}
+
+#[test]
+fn test_vec_shrink_to_fit() {
+    let mut v = Vec::new();
+    for i in 0..1000 {
+        v.push(i);
+    }
+    for _ in 0..990 {
+        v.pop();
+    }
+    assert!(v.capacity() >= 1000);
+
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 10);
+    assert_eq!(v, Vec::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9][..]));
+
+    v.clear();
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 0);
+    v.push(1);
+    assert_eq!(v[0], 1);
+}
+
+#[test]
+fn test_vec_clone() {
+    let mut a: Vec<String> = ["foo", "bar", "qux"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let mut b = a.clone();
+    assert_eq!(a, b);
+    assert_eq!(a.capacity(), b.capacity());
+    assert_ne!(a.as_ptr(), b.as_ptr());
+
+    b[0].push_str("-changed");
+    b.push("new".to_string());
+    a.pop();
+    assert_eq!(a.len(), 2);
+    assert_eq!(b.len(), 4);
+    assert_eq!(a[0], "foo");
+    assert_eq!(b[0], "foo-changed");
+
+    // would be a double free with a shallow clone
+    drop(a);
+    assert_eq!(b[2], "qux");
+    drop(b);
+}
+
+#[test]
+fn test_vec_clone_empty() {
+    let a: Vec<String> = Vec::new();
+    let b = a.clone();
+    assert!(b.is_empty());
+    assert_eq!(b.capacity(), 0);
+}
+
+#[test]
+fn test_vec_as_slice() {
+    let mut v = Vec::from(&[1, 2, 3][..]);
+    assert_eq!(v.as_slice().len(), v.len());
+    assert_eq!(v.as_slice(), &[1, 2, 3]);
+
+    v.as_mut_slice()[1] = 20;
+    assert_eq!(v.as_slice(), &[1, 20, 3]);
+}
+
+#[test]
+fn test_vec_boxed_slice() {
+    let mut v = Vec::with_capacity(20);
+    v.extend([1, 2, 3, 4]);
+    let b: Box<[i32]> = v.into_boxed_slice();
+    assert_eq!(&*b, &[1, 2, 3, 4]);
+
+    let v = Vec::from(b);
+    assert_eq!(v.capacity(), 4);
+    assert_eq!(v, Vec::from(&[1, 2, 3, 4][..]));
+
+    let empty: Box<[String]> = Vec::new().into_boxed_slice();
+    assert!(empty.is_empty());
+    assert!(Vec::from(empty).is_empty());
+}
+
+#[test]
+fn test_vec_contains() {
+    let v = Vec::from(&["sheep", "wool", "grass"][..]);
+    assert!(v.contains(&"wool"));
+    assert!(v.contains(&"grass"));
+    assert!(!v.contains(&"wolf"));
+    assert!(!Vec::<&str>::new().contains(&"sheep"));
+}
+
+#[test]
+fn test_vec_hash() {
+    use std::collections::HashMap;
+    use std::hash::{BuildHasher, RandomState};
+
+    let a = Vec::from(&[1, 2, 3][..]);
+    let b = Vec::from(&[1, 2, 3][..]);
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(&a), state.hash_one(&b));
+    assert_eq!(state.hash_one(&a), state.hash_one(std::vec![1, 2, 3]));
+
+    let mut map = HashMap::new();
+    map.insert(a, "first");
+    map.insert(Vec::from(&[3, 2, 1][..]), "second");
+    assert_eq!(map.get(&b), Some(&"first"));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_vec_ord() {
+    use std::cmp::Ordering;
+
+    let a = Vec::from(&[1, 2][..]);
+    let b = Vec::from(&[1, 3][..]);
+    assert!(a < b);
+    assert_eq!(a.cmp(&b), Ordering::Less);
+
+    let short = Vec::from(&[1][..]);
+    let long = Vec::from(&[1, 0][..]);
+    assert!(short < long);
+    assert_eq!(long.cmp(&short), Ordering::Greater);
+    assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+
+    let mut vecs: Vec<_> = [&b, &long, &a, &short].into_iter().cloned().collect();
+    vecs.deref_mut().sort();
+    let expected: Vec<_> = [short, long, a, b].into_iter().collect();
+    assert_eq!(vecs, expected);
+}
+
+#[test]
+fn test_vec_clone_from() {
+    let source: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    let mut target: Vec<String> = Vec::with_capacity(10);
+    target.push("old".to_string());
+    let ptr = target.as_ptr();
+
+    for _ in 0..5 {
+        target.clone_from(&source);
+        assert_eq!(target, source);
+        assert_eq!(target.as_ptr(), ptr);
+    }
+
+    let mut small: Vec<String> = Vec::new();
+    small.clone_from(&source);
+    assert_eq!(small, source);
+}
+
+#[test]
+fn test_vec_range_index() {
+    let mut v = Vec::from(&[0, 1, 2, 3, 4][..]);
+    assert_eq!(&v[1..3], &[1, 2]);
+    assert_eq!(&v[..2], &[0, 1]);
+    assert_eq!(&v[2..], &[2, 3, 4]);
+    assert_eq!(&v[..], &[0, 1, 2, 3, 4]);
+    assert_eq!(&v[1..=3], &[1, 2, 3]);
+    assert_eq!(&v[..=1], &[0, 1]);
+
+    v[1..3].copy_from_slice(&[10, 20]);
+    assert_eq!(v, Vec::from(&[0, 10, 20, 3, 4][..]));
+}
+
+#[test]
+#[should_panic]
+fn test_vec_range_index_out_of_bounds() {
+    let v = Vec::from(&[0, 1, 2][..]);
+    let _ = &v[1..4];
+}
+
+#[test]
+fn test_vec_binary_search() {
+    let v = Vec::from(&[1, 3, 5, 7, 9][..]);
+    assert_eq!(v.binary_search(&5), Ok(2));
+    assert_eq!(v.binary_search(&1), Ok(0));
+    assert_eq!(v.binary_search(&4), Err(2));
+    assert_eq!(v.binary_search(&100), Err(5));
+
+    assert_eq!(v.binary_search_by(|x| x.cmp(&9)), Ok(4));
+    assert_eq!(v.binary_search_by(|x| x.cmp(&0)), Err(0));
+}
+
+#[test]
+fn test_vec_sort() {
+    use rand::seq::SliceRandom;
+
+    let mut v: Vec<u32> = (0..500).collect();
+    v.as_mut_slice().shuffle(&mut rand::rng());
+    v.sort();
+    assert_eq!(v.len(), 500);
+    for (i, x) in v.iter().enumerate() {
+        assert_eq!(i as u32, *x);
+    }
+}
+
+#[test]
+fn test_vec_sort_by() {
+    let mut v = Vec::from(&[3, 1, 4, 1, 5, 9, 2, 6][..]);
+    v.sort_by(|a, b| b.cmp(a));
+    assert_eq!(v, Vec::from(&[9, 6, 5, 4, 3, 2, 1, 1][..]));
+}
+
+#[test]
+fn test_vec_drain_range() {
+    let mut v = Vec::from(&[0, 1, 2, 3, 4][..]);
+    let drained: Vec<i32> = v.drain(1..3).collect();
+    assert_eq!(drained, Vec::from(&[1, 2][..]));
+    assert_eq!(v, Vec::from(&[0, 3, 4][..]));
+
+    let drained: Vec<i32> = v.drain(1..).collect();
+    assert_eq!(drained, Vec::from(&[3, 4][..]));
+    assert_eq!(v, Vec::from(&[0][..]));
+
+    v.extend([1, 2, 3]);
+    assert_eq!(v.drain(..=1).next_back(), Some(1));
+    assert_eq!(v, Vec::from(&[2, 3][..]));
+
+    assert_eq!(v.drain(1..1).count(), 0);
+    assert_eq!(v, Vec::from(&[2, 3][..]));
+}
+
+#[test]
+fn test_vec_drain_partial() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut v: Vec<Rc<()>> = (0..6).map(|_| counter.clone()).collect();
+
+    let mut drain = v.drain(1..5);
+    drop(drain.next());
+    drop(drain);
+
+    // the unconsumed elements are dropped, the tail is moved back
+    assert_eq!(v.len(), 2);
+    assert_eq!(Rc::strong_count(&counter), 3);
+    drop(v);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+#[should_panic(expected = "range out of bounds")]
+fn test_vec_drain_out_of_bounds() {
+    let mut v = Vec::from(&[0, 1, 2][..]);
+    v.drain(1..4);
+}
+
+#[test]
+fn test_vec_reserve_amortized() {
+    use crate::raw_vec::test::REALLOCATIONS;
+
+    let mut v = Vec::new();
+    let before = REALLOCATIONS.with(|c| c.get());
+    for i in 0..1000 {
+        v.reserve(1);
+        v.push(i);
+    }
+    let reallocations = REALLOCATIONS.with(|c| c.get()) - before;
+    assert!(reallocations <= 20, "{reallocations} reallocations");
+    assert!(v.capacity() >= 1000);
+}
+
+#[test]
+fn test_vec_reserve_exact() {
+    let mut v = Vec::from(&[1, 2, 3][..]);
+    v.reserve_exact(10);
+    assert_eq!(v.capacity(), 13);
+    // enough space already
+    v.reserve_exact(5);
+    v.reserve(5);
+    assert_eq!(v.capacity(), 13);
+
+    v.reserve(20);
+    assert_eq!(v.capacity(), 26);
+}
+
+#[test]
+fn test_vec_from_iter_reserves() {
+    use crate::raw_vec::test::REALLOCATIONS;
+
+    let before = REALLOCATIONS.with(|c| c.get());
+    let v: Vec<u32> = (0..10_000).collect();
+    assert_eq!(REALLOCATIONS.with(|c| c.get()) - before, 1);
+    assert_eq!(v.capacity(), 10_000);
+    assert_eq!(v.len(), 10_000);
+
+    // no size hint to work with, but growth is still amortized
+    let before = REALLOCATIONS.with(|c| c.get());
+    let v: Vec<u32> = (0..10_000).filter(|x| x % 2 == 0).collect();
+    assert!(REALLOCATIONS.with(|c| c.get()) - before <= 20);
+    assert_eq!(v.len(), 5_000);
+
+    let mut v = Vec::from(&[1, 2][..]);
+    let before = REALLOCATIONS.with(|c| c.get());
+    v.extend(0..100);
+    assert_eq!(REALLOCATIONS.with(|c| c.get()) - before, 1);
+}
+
+#[test]
+fn test_vec_ref_iter_double_ended() {
+    let v = Vec::from(&[1, 2, 3, 4, 5][..]);
+
+    let reversed: Vec<&i32> = (&v).into_iter().rev().collect();
+    assert_eq!(reversed, Vec::from(&[&5, &4, &3, &2, &1][..]));
+    assert_eq!(v.iter().rev().count(), 5);
+
+    let mut iter = (&v).into_iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&5));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn test_vec_first_last() {
+    let mut v = Vec::<i32>::new();
+    assert_eq!(v.first(), None);
+    assert_eq!(v.last(), None);
+    assert_eq!(v.first_mut(), None);
+    assert_eq!(v.last_mut(), None);
+
+    v.extend([1, 2, 3]);
+    assert_eq!(v.first(), Some(&1));
+    assert_eq!(v.last(), Some(&3));
+
+    *v.first_mut().unwrap() = 10;
+    *v.last_mut().unwrap() = 30;
+    assert_eq!(v, Vec::from(&[10, 2, 30][..]));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_vec_serde_roundtrip() {
+    let v: Vec<String> = ["sheep", "wool"].iter().map(|s| s.to_string()).collect();
+    let json = serde_json::to_string(&v).unwrap();
+    assert_eq!(json, r#"["sheep","wool"]"#);
+    assert_eq!(
+        json,
+        serde_json::to_string(&std::vec!["sheep", "wool"]).unwrap()
+    );
+
+    let back: Vec<String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, v);
+
+    let empty: Vec<u8> = serde_json::from_str("[]").unwrap();
+    assert!(empty.is_empty());
+    assert!(serde_json::from_str::<Vec<u8>>("{}").is_err());
+}
+
+#[test]
+fn test_vec_eq_slice_array() {
+    let v = Vec::from(&[1, 2, 3][..]);
+    assert_eq!(v, [1, 2, 3]);
+    assert_ne!(v, [1, 2]);
+    assert_ne!(v, [1, 2, 4]);
+
+    let slice: &[i32] = &[1, 2, 3];
+    assert_eq!(v, slice);
+    assert_eq!(v, *slice);
+    assert_ne!(v, &slice[1..]);
+
+    assert_eq!(Vec::<i32>::new(), []);
+}
+
+#[test]
+fn test_vec_get() {
+    let mut v = Vec::from(&[1, 2, 3][..]);
+    assert_eq!(v.get(0), Some(&1));
+    assert_eq!(v.get(2), Some(&3));
+    assert_eq!(v.get(3), None);
+    assert_eq!(v.get(100), None);
+
+    *v.get_mut(1).unwrap() = 20;
+    assert_eq!(v, [1, 20, 3]);
+    assert_eq!(v.get_mut(100), None);
+}
+
+#[test]
+fn test_vec_rotate() {
+    let mut v = Vec::from(&[1, 2, 3, 4, 5][..]);
+    v.rotate_left(2);
+    assert_eq!(v, [3, 4, 5, 1, 2]);
+    v.rotate_right(2);
+    assert_eq!(v, [1, 2, 3, 4, 5]);
+    v.rotate_left(5);
+    assert_eq!(v, [1, 2, 3, 4, 5]);
+    v.rotate_right(1);
+    assert_eq!(v, [5, 1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_vec_rotate_out_of_bounds() {
+    let mut v = Vec::from(&[1, 2, 3][..]);
+    v.rotate_left(4);
+}
+
+#[test]
+fn test_vec_into_iter_as_slice() {
+    let v = Vec::from(&[1, 2, 3, 4, 5][..]);
+    let mut iter = v.into_iter();
+    assert_eq!(iter.as_slice(), &[1, 2, 3, 4, 5]);
+
+    iter.next();
+    iter.next();
+    assert_eq!(iter.as_slice(), &[3, 4, 5]);
+
+    iter.as_mut_slice()[0] = 30;
+    iter.next_back();
+    assert_eq!(iter.as_slice(), &[30, 4]);
+    assert_eq!(iter.next(), Some(30));
+
+    let mut zsts = Vec::new();
+    zsts.extend([(), (), ()]);
+    let mut iter = zsts.into_iter();
+    iter.next();
+    assert_eq!(iter.as_slice().len(), 2);
+}
+
+#[test]
+fn test_vec_iter_len() {
+    let mut v = Vec::from(&[1, 2, 3, 4, 5][..]);
+
+    let mut drain = v.drain_all();
+    assert_eq!(drain.len(), 5);
+    drain.next();
+    assert_eq!(drain.len(), 4);
+    drop(drain);
+
+    v.extend([1, 2, 3]);
+    let mut iter = v.into_iter();
+    assert_eq!(iter.len(), 3);
+    iter.next_back();
+    assert_eq!(iter.len(), 2);
+    iter.by_ref().for_each(drop);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_vec_std_conversion() {
+    let std_vec: std::vec::Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    let ptr = std_vec.as_ptr();
+
+    let v = Vec::from(std_vec);
+    assert_eq!(v.len(), 3);
+    assert_eq!(v[2], "c");
+    // the allocation was moved over, not copied
+    assert_eq!(v.as_ptr(), ptr);
+
+    let back: std::vec::Vec<String> = v.into();
+    assert_eq!(back, ["a", "b", "c"]);
+    assert_eq!(back.as_ptr(), ptr);
+
+    let empty: std::vec::Vec<u8> = Vec::new().into();
+    assert!(Vec::from(empty).is_empty());
+}
+
+#[test]
+fn test_vec_iter_mut_loop() {
+    let mut v = Vec::from(&[1, 2, 3, 4][..]);
+    for x in &mut v {
+        *x *= 2;
+    }
+    assert_eq!(v, [2, 4, 6, 8]);
+}
+
+#[test]
+fn test_vec_swap() {
+    let mut v = Vec::from(&[1, 2, 3, 4, 5][..]);
+    v.swap(0, 4);
+    assert_eq!(v, [5, 2, 3, 4, 1]);
+    v.swap(2, 2);
+    assert_eq!(v, [5, 2, 3, 4, 1]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_vec_swap_out_of_bounds() {
+    let mut v = Vec::from(&[1, 2, 3][..]);
+    v.swap(0, 3);
+}
+
+#[test]
+fn test_vec_split_at() {
+    let mut v = Vec::from(&[1, 2, 3, 4][..]);
+    let (left, right) = v.split_at(2);
+    assert_eq!(left, &[1, 2]);
+    assert_eq!(right, &[3, 4]);
+
+    let (left, right) = v.split_at_mut(2);
+    left[0] = 10;
+    right[0] = 30;
+    assert_eq!(v, [10, 2, 30, 4]);
+
+    let (left, right) = v.split_at(4);
+    assert_eq!(left.len(), 4);
+    assert!(right.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_vec_split_at_out_of_bounds() {
+    let v = Vec::from(&[1, 2, 3][..]);
+    let _ = v.split_at(4);
+}
+
+#[test]
+fn test_vec_try_reserve() {
+    let mut v = Vec::from(&[1u64, 2, 3][..]);
+    assert_eq!(v.try_reserve(10), Ok(()));
+    assert!(v.capacity() >= 13);
+
+    assert_eq!(
+        v.try_reserve(usize::MAX),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(
+        v.try_reserve(isize::MAX as usize / 4),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    // the vec is still intact
+    assert_eq!(v, [1, 2, 3]);
+
+    let mut zsts: Vec<()> = Vec::new();
+    assert_eq!(zsts.try_reserve(usize::MAX), Ok(()));
+    zsts.push(());
+    assert!(zsts.try_reserve(usize::MAX).is_err());
+}
+
+#[test]
+fn test_vec_clear_keeps_capacity() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut v: Vec<Rc<()>> = (0..50).map(|_| counter.clone()).collect();
+    let capacity = v.capacity();
+    assert_eq!(Rc::strong_count(&counter), 51);
+
+    v.clear();
+    assert_eq!(v.len(), 0);
+    assert_eq!(v.capacity(), capacity);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn test_vec_spare_capacity_mut() {
+    let mut v = Vec::<u32>::new();
+    v.reserve_exact(8);
+    let spare = v.spare_capacity_mut();
+    assert_eq!(spare.len(), 8);
+    for (i, slot) in spare.iter_mut().take(4).enumerate() {
+        slot.write(i as u32 * 10);
+    }
+    unsafe { v.set_len(4) };
+
+    assert_eq!(v, [0, 10, 20, 30]);
+    assert_eq!(v.spare_capacity_mut().len(), 4);
+}
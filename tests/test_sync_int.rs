@@ -1,18 +1,16 @@
-use std::thread::JoinHandle;
-
-use datastructurs::sync::sync_ints::{SyncU64, SyncUsize};
+use datastructurs::sync::sync_ints::{SyncI128, SyncU32, SyncU64, SyncUsize};
 
 #[test]
 fn test_sync_int_st() {
     let idx = SyncU64::new(1);
     idx.inc();
-    assert_eq!(*idx.get(), 2);
+    assert_eq!(idx.get(), 2);
     idx.inc();
-    assert_eq!(*idx.get(), 3);
-    *idx.get_mut() = 1337;
-    assert_eq!(*idx.get(), 1337);
+    assert_eq!(idx.get(), 3);
+    idx.set(1337);
+    assert_eq!(idx.get(), 1337);
     idx.set(19);
-    assert_eq!(*idx.get(), 19);
+    assert_eq!(idx.get(), 19);
     assert_eq!(idx.val(), 19);
 }
 
@@ -20,13 +18,13 @@ fn test_sync_int_st() {
 fn test_sync_int_mt() {
     let idx = SyncUsize::new(1);
     idx.inc();
-    assert_eq!(*idx.get(), 2);
+    assert_eq!(idx.get(), 2);
     idx.set(0);
 
     const THREADS: usize = 4;
     let iters: usize = 200;
     let mut ths = Vec::new();
-    for i in 0..THREADS {
+    for _ in 0..THREADS {
         let idx_ref = idx.clone();
         ths.push(std::thread::spawn(move || {
             for _ in 0..iters {
@@ -36,8 +34,100 @@ fn test_sync_int_mt() {
     }
 
     for th in ths {
-        th.join();
+        th.join().unwrap();
+    }
+
+    assert_eq!(idx.get(), THREADS * iters);
+}
+
+#[test]
+fn test_sync_int_stress() {
+    const THREADS: u64 = 8;
+    const ITERS: u64 = 100_000;
+    let idx = SyncU64::new(0);
+    let ths: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let idx_ref = idx.clone();
+            std::thread::spawn(move || {
+                for _ in 0..ITERS {
+                    idx_ref.inc();
+                }
+            })
+        })
+        .collect();
+
+    for th in ths {
+        th.join().unwrap();
+    }
+
+    assert_eq!(idx.get(), THREADS * ITERS);
+}
+
+#[test]
+fn test_sync_int_fetch_add() {
+    let idx = SyncU64::new(0);
+    let ths: Vec<_> = (0..2)
+        .map(|_| {
+            let idx_ref = idx.clone();
+            std::thread::spawn(move || idx_ref.fetch_add(5))
+        })
+        .collect();
+    let mut prev: Vec<u64> = ths.into_iter().map(|th| th.join().unwrap()).collect();
+    prev.sort();
+
+    assert_eq!(prev, [0, 5]);
+    assert_eq!(idx.get(), 10);
+    assert_eq!(idx.fetch_sub(3), 10);
+    assert_eq!(idx.get(), 7);
+
+    let big = SyncI128::new(i128::MAX);
+    assert_eq!(big.fetch_add(1), i128::MAX);
+    assert_eq!(big.get(), i128::MIN);
+    assert_eq!(big.fetch_sub(1), i128::MIN);
+    assert_eq!(big.get(), i128::MAX);
+}
+
+#[test]
+fn test_sync_int_compare_exchange() {
+    const ITERS: u64 = 10_000;
+    let idx = SyncU64::new(0);
+    let ths: Vec<_> = (0..2)
+        .map(|_| {
+            let idx_ref = idx.clone();
+            std::thread::spawn(move || {
+                for _ in 0..ITERS {
+                    let mut seen = idx_ref.get();
+                    while let Err(actual) = idx_ref.compare_exchange(seen, seen + 1) {
+                        seen = actual;
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for th in ths {
+        th.join().unwrap();
     }
 
-    assert_eq!(*idx.get(), THREADS * iters);
+    assert_eq!(idx.get(), 2 * ITERS);
+    assert_eq!(idx.compare_exchange(0, 1), Err(2 * ITERS));
+    assert_eq!(idx.compare_exchange(2 * ITERS, 1), Ok(2 * ITERS));
+    assert_eq!(idx.get(), 1);
+
+    let big = SyncI128::new(3);
+    assert_eq!(big.compare_exchange(4, 5), Err(3));
+    assert_eq!(big.compare_exchange(3, 5), Ok(3));
+    assert_eq!(big.get(), 5);
+}
+
+#[test]
+fn test_sync_int_dec() {
+    let idx = SyncU32::new(0);
+    for _ in 0..10 {
+        idx.inc();
+    }
+    for _ in 0..3 {
+        idx.dec();
+    }
+    assert_eq!(idx.get(), 7);
 }
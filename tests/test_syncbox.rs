@@ -0,0 +1,29 @@
+use datastructurs::sync::SyncBox;
+
+#[test]
+fn test_syncbox_strong_count() {
+    let a = SyncBox::new(String::from("foo"));
+    assert_eq!(a.strong_count(), 1);
+    let b = a.clone();
+    let c = b.clone();
+    assert_eq!(a.strong_count(), 3);
+    assert_eq!(c.strong_count(), 3);
+    drop(b);
+    assert_eq!(a.strong_count(), 2);
+    drop(a);
+    assert_eq!(c.strong_count(), 1);
+    assert_eq!(c.get(), "foo");
+}
+
+#[test]
+fn test_syncbox_try_unwrap() {
+    let a = SyncBox::new(String::from("foo"));
+    assert_eq!(a.try_unwrap().unwrap(), "foo");
+
+    let a = SyncBox::new(String::from("bar"));
+    let b = a.clone();
+    let a = a.try_unwrap().unwrap_err();
+    assert_eq!(a.strong_count(), 2);
+    drop(b);
+    assert_eq!(a.try_unwrap().unwrap(), "bar");
+}